@@ -0,0 +1,125 @@
+//! Generates a test for every named example under `src/inputs/<year>/dayNN/examples/`, and
+//! the table of example inputs that `embedded` compiles into the crate.
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write;
+use std::fs::{self, DirEntry};
+use std::path::{Path, PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/inputs");
+
+    let inputs = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/inputs");
+    let output = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(output.join("example_tests.rs"), example_tests(&inputs)).unwrap();
+    fs::write(output.join("embedded_inputs.rs"), embedded_inputs(&inputs)).unwrap();
+}
+
+/// The entries of `directory`, or none if it cannot be read.
+fn entries(directory: &Path) -> impl Iterator<Item = DirEntry> {
+    fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+}
+
+/// The year directories in `inputs`, by year.
+fn years(inputs: &Path) -> impl Iterator<Item = (u16, PathBuf)> {
+    entries(inputs).filter_map(|entry| {
+        let year = entry.file_name().to_str()?.parse::<u16>().ok()?;
+        entry.path().is_dir().then(|| (year, entry.path()))
+    })
+}
+
+fn example_tests(inputs: &Path) -> String {
+    let mut examples = years(inputs)
+        .flat_map(|(year, directory)| {
+            entries(&directory).filter_map(move |entry| {
+                let day = entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("day")?
+                    .parse::<u8>()
+                    .ok()?;
+                Some((year, day, entry.path().join("examples")))
+            })
+        })
+        .flat_map(|(year, day, directory)| {
+            entries(&directory).filter_map(move |entry| {
+                let path = entry.path();
+                let name = path.file_stem()?.to_str()?.to_string();
+                (path.extension()? == "txt").then_some((year, day, name))
+            })
+        })
+        .collect::<Vec<(u16, u8, String)>>();
+    examples.sort();
+
+    let mut tests = String::new();
+    let mut functions = HashMap::new();
+    for (year, day, name) in examples {
+        let function = name
+            .chars()
+            .map(|char| {
+                if char.is_ascii_alphanumeric() {
+                    char.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        let function = format!("y{year}_day{day:02}_{function}");
+
+        if let Some(other) = functions.insert(function.clone(), name.clone()) {
+            panic!(
+                "The {year} day {day} examples {other:?} and {name:?} would both be tested by \
+                 {function}, rename one of them"
+            );
+        }
+
+        let _ = writeln!(
+            tests,
+            "#[test]\nfn {function}() {{\n    check({year}, {day}, {name:?});\n}}\n"
+        );
+    }
+
+    tests
+}
+
+/// A `FILES` table of every example input, answers file and named example, keyed by its path
+/// within `src/inputs`. Puzzle inputs are personal and are never embedded.
+fn embedded_inputs(inputs: &Path) -> String {
+    let is_example =
+        |name: &str| name.ends_with("_example.txt") || name.ends_with("_example_answers.txt");
+
+    let mut files = years(inputs)
+        .flat_map(|(year, directory)| {
+            entries(&directory).flat_map(move |entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if entry.path().is_dir() {
+                    entries(&entry.path().join("examples"))
+                        .filter(|example| example.path().is_file())
+                        .map(|example| {
+                            let example = example.file_name().to_string_lossy().to_string();
+                            format!("{year}/{name}/examples/{example}")
+                        })
+                        .collect()
+                } else if is_example(&name) {
+                    vec![format!("{year}/{name}")]
+                } else {
+                    vec![]
+                }
+            })
+        })
+        .collect::<Vec<String>>();
+    files.sort();
+
+    let mut table = String::from("static FILES: &[(&str, &str)] = &[\n");
+    for file in files {
+        let path = inputs.join(&file).display().to_string();
+        let _ = writeln!(table, "    ({file:?}, include_str!({path:?})),");
+    }
+    table.push_str("];\n");
+
+    table
+}
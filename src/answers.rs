@@ -0,0 +1,117 @@
+//! The known answers to every example and puzzle input, for checking a run against.
+
+use crate::embedded;
+use crate::extract::parse_answers;
+use crate::submit::AnswerStore;
+use crate::{Part, TaskType};
+
+/// The answers to part one and part two of one input.
+type Answers = [Option<u64>; 2];
+
+/// Example and puzzle answers, by year and day, for part one then part two. `None` marks an
+/// answer that is not known yet.
+const ANSWERS: [(u16, u8, Answers, Answers); 7] = [
+    (2023, 1, [Some(142), Some(281)], [Some(54632), Some(54019)]),
+    (2023, 2, [Some(8), Some(2286)], [Some(2268), Some(63542)]),
+    (
+        2023,
+        3,
+        [Some(4361), Some(467_835)],
+        [Some(556_057), Some(82_824_352)],
+    ),
+    (
+        2023,
+        4,
+        [Some(13), Some(30)],
+        [Some(20855), Some(5_489_600)],
+    ),
+    (
+        2023,
+        5,
+        [Some(35), Some(46)],
+        [Some(340_994_526), Some(52_210_644)],
+    ),
+    (
+        2023,
+        6,
+        [Some(288), Some(71503)],
+        [Some(633_080), Some(20_048_741)],
+    ),
+    (2023, 7, [Some(6440), Some(5905)], [Some(248_559_379), None]),
+];
+
+/// The correct answer for one part of a day's example or puzzle input, if it is known.
+///
+/// Example answers missing from the table are looked up in `<year>/dayNN_example_answers.txt`,
+/// as written by the `extract` command. Puzzle answers missing from the table are looked up in
+/// the answer store, as recorded by the `submit` command.
+#[must_use]
+pub fn expected(year: u16, day: u8, part: &Part, task_type: &TaskType) -> Option<u64> {
+    let store = match task_type {
+        TaskType::Example => None,
+        TaskType::Puzzle => AnswerStore::from_env().ok().flatten(),
+    };
+
+    known(year, day, *part, *task_type, store.as_ref())
+}
+
+/// As [`expected`], with puzzle answers missing from the table looked up in `store`.
+fn known(
+    year: u16,
+    day: u8,
+    part: Part,
+    task_type: TaskType,
+    store: Option<&AnswerStore>,
+) -> Option<u64> {
+    let index = match part {
+        Part::PartOne => 0,
+        Part::PartTwo => 1,
+    };
+    let known = ANSWERS
+        .iter()
+        .find(|(known_year, known_day, _, _)| *known_year == year && *known_day == day)
+        .and_then(|(_, _, example, puzzle)| match task_type {
+            TaskType::Example => example[index],
+            TaskType::Puzzle => puzzle[index],
+        });
+
+    known.or_else(|| match task_type {
+        TaskType::Example => {
+            let answers = embedded::read(&format!("{year}/day{day:02}_example_answers.txt"));
+            answers
+                .ok()
+                .and_then(|answers| parse_answers(&answers)[index])
+        }
+        TaskType::Puzzle => store.and_then(|store| store.correct(year, day, &part)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    #[test]
+    fn stored_answers_fill_gaps_in_the_table() {
+        let path = env::temp_dir().join(format!("aoc-answers-known-{}.txt", std::process::id()));
+        fs::write(
+            &path,
+            "2023 7 2 248750248 correct 100\n2023 7 1 1 correct 100\n",
+        )
+        .unwrap();
+        let store = AnswerStore::open(path.clone()).unwrap();
+        fs::remove_file(path).unwrap();
+
+        let part_two = known(2023, 7, Part::PartTwo, TaskType::Puzzle, Some(&store));
+        assert_eq!(part_two, Some(248_750_248));
+        // The table takes precedence over the store.
+        let part_one = known(2023, 7, Part::PartOne, TaskType::Puzzle, Some(&store));
+        assert_eq!(part_one, Some(248_559_379));
+        assert_eq!(known(2023, 7, Part::PartTwo, TaskType::Puzzle, None), None);
+        assert_eq!(
+            known(2023, 7, Part::PartTwo, TaskType::Example, Some(&store)),
+            Some(5905)
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Differential tests, which run each optimised solver against the naive solver it replaced on
+//! randomly generated inputs.
+//!
+//! Any disagreement fails with the seed and the input shrunk to as few lines as still disagree.
+//! Set `AOC_DIFF_ITERATIONS` to change how many inputs each pair is tried on, and
+//! `AOC_DIFF_SEED` to start from a different seed:
+//!
+//! ```text
+//! AOC_DIFF_ITERATIONS=10000 cargo test --release differential
+//! ```
+
+use std::env;
+
+use crate::generate;
+use crate::shrink::{self, outcome, Failure};
+use crate::solvers::Solver;
+use crate::year2023::day02::{self, Bag};
+use crate::year2023::day04::{self, Overflow};
+use crate::year2023::{day05, day06};
+
+const DEFAULT_ITERATIONS: u64 = 50;
+
+fn env_number(name: &str, default: u64) -> u64 {
+    env::var(name).map_or(default, |value| {
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("{name} should be a number, found {value}"))
+    })
+}
+
+/// The two outcomes, if the optimised solver disagrees with the reference.
+fn disagreement(
+    reference: Solver,
+    optimised: Solver,
+    data: &str,
+) -> Option<(u64, Result<u64, String>)> {
+    let expected = outcome(reference, data).ok()?;
+    let actual = outcome(optimised, data);
+
+    (actual.as_ref() != Ok(&expected)).then_some((expected, actual))
+}
+
+/// Runs both solvers on an input made by `input` from a seed and the iteration number, which
+/// sizes the input.
+fn check(name: &str, reference: Solver, optimised: Solver, input: impl Fn(u64, usize) -> String) {
+    let iterations = env_number("AOC_DIFF_ITERATIONS", DEFAULT_ITERATIONS);
+    let first_seed = env_number("AOC_DIFF_SEED", 2023);
+
+    for (iteration, offset) in (0..iterations).enumerate() {
+        let seed = first_seed.wrapping_add(offset);
+        let data = input(seed, iteration);
+
+        let failure = Failure::Reference(reference);
+        if let Some(minimal) = shrink::shrink(&data, |data| failure.reproduces(optimised, data)) {
+            let (expected, actual) = disagreement(reference, optimised, &minimal)
+                .expect("The minimised input still disagrees");
+
+            panic!(
+                "{name} disagrees with its reference on seed {seed}: expected {expected}, \
+                 found {actual:?}\nMinimised input:\n{minimal}"
+            );
+        }
+    }
+}
+
+#[test]
+fn day02_compact_parser() {
+    check(
+        "Day02 part one",
+        |data| day02::reference::part_one(data, &Bag::standard()),
+        |data| day02::part_one(data, &Bag::standard()).unwrap(),
+        |seed, iteration| generate::games(seed, 1 + iteration % 50, 6, 16),
+    );
+    check(
+        "Day02 part two",
+        day02::reference::part_two,
+        |data| day02::part_two(data).unwrap(),
+        |seed, iteration| generate::games(seed, 1 + iteration % 50, 6, 16),
+    );
+}
+
+#[test]
+fn day04_match_sets() {
+    let input = |seed, iteration| generate::scratchcards(seed, 1 + iteration % 60, 5, 8);
+
+    check(
+        "Day04 part one",
+        |data| u64::from(day04::reference::part_one(data)),
+        |data| u64::from(day04::part_one(data).unwrap()),
+        input,
+    );
+    check(
+        "Day04 part two",
+        |data| u64::from(day04::reference::part_two(data)),
+        |data| u64::from(day04::part_two(data, &Overflow::Error).unwrap()),
+        input,
+    );
+}
+
+#[test]
+fn day05_intervals() {
+    check(
+        "Day05 part two",
+        day05::reference::part_two,
+        |data| day05::part_two(data).unwrap(),
+        |seed, iteration| generate::almanac(seed, 1 + iteration % 4, 1 + iteration % 5, 200, 1000),
+    );
+}
+
+#[test]
+fn day06_closed_form() {
+    check(
+        "Day06 part one",
+        day06::reference::part_one,
+        |data| day06::part_one(data).unwrap(),
+        |seed, iteration| generate::races(seed, 1 + iteration % 4, 10_000),
+    );
+    check(
+        "Day06 part two",
+        day06::reference::part_two,
+        |data| day06::part_two(data).unwrap(),
+        |seed, iteration| generate::races(seed, 1 + iteration % 3, 60),
+    );
+}
+
+#[test]
+fn disagreements_are_minimised() {
+    fn reference(data: &str) -> u64 {
+        data.lines().count() as u64
+    }
+
+    fn off_by_one_on_sevens(data: &str) -> u64 {
+        reference(data) + u64::from(data.contains('7'))
+    }
+
+    let data = "1\n2\n3\n4\n5\n6\n7\n8\n9";
+    let minimal = shrink::shrink(data, |data| {
+        Failure::Reference(reference).reproduces(off_by_one_on_sevens, data)
+    })
+    .unwrap();
+
+    assert_eq!(minimal, "7");
+    assert_eq!(
+        disagreement(reference, off_by_one_on_sevens, &minimal),
+        Some((1, Ok(2)))
+    );
+}
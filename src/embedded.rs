@@ -0,0 +1,83 @@
+//! Example inputs compiled into the crate, so that tests and the runner do not depend on the
+//! directory they are started from. Puzzle inputs are not embedded: they are read from disk,
+//! or fetched.
+//!
+//! Paths are relative to `src/inputs`, as in `2023/day07_example.txt` or
+//! `2023/day07/examples/identical_hands.txt`. Embedded files take precedence over files on disk.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+/// The embedded contents of `path`, if it was embedded.
+#[must_use]
+pub fn file(path: &str) -> Option<&'static str> {
+    FILES
+        .iter()
+        .find(|(name, _)| *name == path)
+        .map(|(_, contents)| *contents)
+}
+
+/// Reads `path` from the embedded files first and from `src/inputs` on disk second.
+///
+/// # Errors
+///
+/// Returns an error if `path` is not embedded and cannot be read from disk.
+pub fn read(path: &str) -> io::Result<String> {
+    file(path).map_or_else(
+        || fs::read_to_string(Path::new("src/inputs").join(path)),
+        |contents| Ok(contents.to_string()),
+    )
+}
+
+/// The names of the files directly in `directory`, embedded or on disk, in order.
+#[must_use]
+pub fn list(directory: &str) -> Vec<String> {
+    let embedded = FILES.iter().filter_map(|(name, _)| {
+        let name = name.strip_prefix(directory)?.strip_prefix('/')?;
+        (!name.contains('/')).then(|| name.to_string())
+    });
+    let on_disk = fs::read_dir(Path::new("src/inputs").join(directory))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok());
+
+    let mut names = embedded.chain(on_disk).collect::<Vec<String>>();
+    names.sort();
+    names.dedup();
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_are_embedded() {
+        assert!(file("2023/day07_example.txt").is_some_and(|input| input.starts_with("32T3K 765")));
+        assert!(file("2023/day01_part2_example.txt").is_some());
+        assert_eq!(
+            file("2023/day06/examples/single_race.answers"),
+            Some("part1: 4\npart2: 4\n")
+        );
+        assert_eq!(file("2023/day07_puzzle.txt"), None);
+        assert_eq!(
+            read("2023/day99_example.txt").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert_eq!(
+            list("2023/day07/examples"),
+            [
+                "identical_hands.answers",
+                "identical_hands.txt",
+                "jokers_reorder.answers",
+                "jokers_reorder.txt"
+            ]
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Named examples beyond the one each day's tests use, kept in
+//! `src/inputs/<year>/dayNN/examples/`.
+//!
+//! Each example is a `<name>.txt` input, with its expected answers in `<name>.answers` as
+//! `partN: answer` lines. A part without an answer is not checked, which suits examples that
+//! only make sense for one part, but every example needs an answer for at least one part. The
+//! build script generates a test for every example, which fails on an example with none.
+
+use std::io;
+use std::path::PathBuf;
+
+use crate::embedded;
+use crate::extract::parse_answers;
+use crate::Part;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub data: String,
+    pub answers: [Option<u64>; 2],
+}
+
+impl Example {
+    /// Loads the example called `name` for `day` in `year`. Missing answers are not an error
+    /// here; the generated tests check for them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the example input is missing or unreadable.
+    pub fn load(year: u16, day: u8, name: &str) -> io::Result<Self> {
+        let path = format!("{year}/day{day:02}/examples/{name}");
+        let data = embedded::read(&format!("{path}.txt"))?;
+        let answers = embedded::read(&format!("{path}.answers"))
+            .map_or([None, None], |answers| parse_answers(&answers));
+
+        Ok(Self {
+            name: name.to_string(),
+            data,
+            answers,
+        })
+    }
+
+    /// The expected answer for `part`, if the example has one.
+    #[must_use]
+    pub const fn answer(&self, part: &Part) -> Option<u64> {
+        match part {
+            Part::PartOne => self.answers[0],
+            Part::PartTwo => self.answers[1],
+        }
+    }
+}
+
+/// The directory holding the named examples for `day` in `year`.
+#[must_use]
+pub fn directory(year: u16, day: u8) -> PathBuf {
+    PathBuf::from(format!("src/inputs/{year}/day{day:02}/examples"))
+}
+
+/// The names of the examples for `day` in `year`, embedded or on disk, in order.
+#[must_use]
+pub fn names(year: u16, day: u8) -> Vec<String> {
+    embedded::list(&format!("{year}/day{day:02}/examples"))
+        .iter()
+        .filter_map(|file| Some(file.strip_suffix(".txt")?.to_string()))
+        .collect()
+}
+
+/// Every example for `day` in `year`.
+///
+/// # Errors
+///
+/// Returns an error if an example cannot be read.
+pub fn examples(year: u16, day: u8) -> io::Result<Vec<Example>> {
+    names(year, day)
+        .iter()
+        .map(|name| Example::load(year, day, name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers;
+
+    /// Checks every answer an example declares against the runner's solvers.
+    fn check(year: u16, day: u8, name: &str) {
+        let example = Example::load(year, day, name).unwrap();
+        assert!(
+            example.answers.iter().any(Option::is_some),
+            "Day {day} example {name} should declare at least one answer"
+        );
+
+        for part in [Part::PartOne, Part::PartTwo] {
+            if let Some(expected) = example.answer(&part) {
+                let solver = solvers::solver(year, day, &part).unwrap();
+                assert_eq!(
+                    solver(&example.data),
+                    expected,
+                    "Day {day} example {name}, {part:?}"
+                );
+            }
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+
+    #[test]
+    fn examples_are_discovered() {
+        assert_eq!(names(2023, 7), ["identical_hands", "jokers_reorder"]);
+        assert_eq!(
+            examples(2023, 6).unwrap(),
+            [Example {
+                name: "single_race".to_string(),
+                data: "Time:      7\nDistance:  9\n".to_string(),
+                answers: [Some(4), Some(4)],
+            }]
+        );
+        assert!(names(2023, 1).is_empty());
+        assert!(names(2015, 7).is_empty());
+    }
+}
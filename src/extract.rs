@@ -0,0 +1,211 @@
+//! Pulls the example input and its answers out of a saved puzzle description, so that a new
+//! day's example files do not have to be typed in by hand.
+//!
+//! A description has an `<article>` per part. The first `<pre><code>` block in a part is its
+//! example, and the last emphasised number in it, as in `<code><em>142</em></code>`, is the
+//! answer for that example.
+
+use std::fmt::Write;
+
+/// The example and answer for each part, where the description has them.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Extracted {
+    pub examples: [Option<String>; 2],
+    pub answers: [Option<u64>; 2],
+}
+
+#[must_use]
+pub fn extract(html: &str) -> Extracted {
+    let articles = articles(html);
+    let part = |index: usize| articles.get(index).copied();
+
+    Extracted {
+        examples: [0, 1].map(|index| part(index).and_then(example)),
+        answers: [0, 1].map(|index| part(index).and_then(answer)),
+    }
+}
+
+impl Extracted {
+    /// The files to write for `day`, by name within the inputs directory.
+    ///
+    /// Parts that share an example share a file. Parts with different examples get a file
+    /// each, named as Day01's are. The answers go in `dayNN_example_answers.txt`.
+    #[must_use]
+    pub fn files(&self, day: u8) -> Vec<(String, String)> {
+        let mut files = match &self.examples {
+            [None, None] => vec![],
+            [Some(example), None] | [None, Some(example)] => {
+                vec![(format!("day{day:02}_example.txt"), example.clone())]
+            }
+            [Some(first), Some(second)] if first == second => {
+                vec![(format!("day{day:02}_example.txt"), first.clone())]
+            }
+            [Some(first), Some(second)] => vec![
+                (format!("day{day:02}_part1_example.txt"), first.clone()),
+                (format!("day{day:02}_part2_example.txt"), second.clone()),
+            ],
+        };
+
+        if self.answers.iter().any(Option::is_some) {
+            let mut answers = String::new();
+            for (part, answer) in (1..=2).zip(self.answers) {
+                if let Some(answer) = answer {
+                    let _ = writeln!(answers, "part{part}: {answer}");
+                }
+            }
+            files.push((format!("day{day:02}_example_answers.txt"), answers));
+        }
+
+        files
+    }
+}
+
+/// Reads the answers in a `dayNN_example_answers.txt` file, one `partN: answer` per line.
+#[must_use]
+pub fn parse_answers(answers: &str) -> [Option<u64>; 2] {
+    let answer = |part: &str| {
+        answers.lines().find_map(|line| {
+            let (key, answer) = line.split_once(':')?;
+            (key.trim() == part)
+                .then(|| answer.trim().parse().ok())
+                .flatten()
+        })
+    };
+
+    [answer("part1"), answer("part2")]
+}
+
+/// The contents of each `<article>`, or the whole page if it has none.
+fn articles(html: &str) -> Vec<&str> {
+    let articles = html
+        .split("<article")
+        .skip(1)
+        .filter_map(|article| {
+            let start = article.find('>')? + 1;
+            let end = article.find("</article>").unwrap_or(article.len());
+            article.get(start..end)
+        })
+        .collect::<Vec<&str>>();
+
+    if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    }
+}
+
+fn example(article: &str) -> Option<String> {
+    let start = article.find("<pre><code>")? + "<pre><code>".len();
+    let end = start + article[start..].find("</code></pre>")?;
+
+    Some(text(&article[start..end]))
+}
+
+fn answer(article: &str) -> Option<u64> {
+    let numbers = |marker: &str, close: &str| {
+        article
+            .match_indices(marker)
+            .filter_map(|(index, _)| {
+                let start = index + marker.len();
+                let end = start + article[start..].find(close)?;
+                text(&article[start..end]).trim().parse::<u64>().ok()
+            })
+            .last()
+    };
+
+    numbers("<code><em>", "</em></code>").or_else(|| numbers("<em>", "</em>"))
+}
+
+/// The text of an HTML fragment, without its tags and with entities decoded.
+fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for char in html.chars() {
+        match char {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            char if !in_tag => text.push(char),
+            _ => {}
+        }
+    }
+
+    [
+        ("&lt;", "<"),
+        ("&gt;", ">"),
+        ("&quot;", "\""),
+        ("&#39;", "'"),
+        ("&amp;", "&"),
+    ]
+    .iter()
+    .fold(text, |text, (entity, char)| text.replace(entity, char))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESCRIPTION: &str = "<html><body><main>\
+        <article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2>\
+        <p>For example:</p>\
+        <pre><code>1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n</code></pre>\
+        <p>The values are <code>12</code>, <code>38</code>, <code>15</code> and <code>77</code>. \
+        Adding these together produces <code><em>142</em></code>.</p></article>\
+        <p>Your puzzle answer was <code>54632</code>.</p>\
+        <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\
+        <p>It looks like <em>some</em> of the digits are spelled out:</p>\
+        <pre><code>two1nine\n<em>eight</em>wothree\n4nine &amp; 7 &lt; 2\n</code></pre>\
+        <p>Adding these together produces <code><em>281</em></code>.</p></article>\
+        </main></body></html>";
+
+    #[test]
+    fn examples_and_answers_are_extracted() {
+        let extracted = extract(DESCRIPTION);
+
+        assert_eq!(
+            extracted,
+            Extracted {
+                examples: [
+                    Some("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n".to_string()),
+                    Some("two1nine\neightwothree\n4nine & 7 < 2\n".to_string()),
+                ],
+                answers: [Some(142), Some(281)],
+            }
+        );
+        assert_eq!(
+            extracted
+                .files(1)
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<&str>>(),
+            [
+                "day01_part1_example.txt",
+                "day01_part2_example.txt",
+                "day01_example_answers.txt"
+            ]
+        );
+        assert_eq!(
+            parse_answers(&extracted.files(1)[2].1),
+            [Some(142), Some(281)]
+        );
+    }
+
+    #[test]
+    fn shared_examples_share_a_file() {
+        let extracted = Extracted {
+            examples: [Some("32T3K 765\n".to_string()), None],
+            answers: [Some(6440), None],
+        };
+
+        assert_eq!(
+            extracted.files(7),
+            [
+                ("day07_example.txt".to_string(), "32T3K 765\n".to_string()),
+                (
+                    "day07_example_answers.txt".to_string(),
+                    "part1: 6440\n".to_string()
+                ),
+            ]
+        );
+        assert_eq!(parse_answers("part2: 5905\n"), [None, Some(5905)]);
+    }
+}
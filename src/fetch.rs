@@ -0,0 +1,425 @@
+//! Downloads puzzle inputs over plain HTTP and caches them, so that a missing input file can
+//! be filled in without copying it by hand.
+//!
+//! Fetching is configured through the environment:
+//!
+//! - `AOC_BASE_URL`: where inputs are served from, as `http://host[:port][/path]`. Each day's
+//!   input is at `<base>/<year>/day/<N>/input`. Only plain HTTP is spoken, so reaching the real site
+//!   needs a local HTTPS proxy.
+//! - `AOC_SESSION`: the session token, sent as the `session` cookie.
+//! - `AOC_CACHE_DIR`: where inputs are cached, in a directory per year. Defaults to
+//!   `aoc-2023-rust/inputs` under `$XDG_DATA_HOME`, or under `~/.local/share`.
+//!
+//! A cached input is never downloaded again.
+
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// The base URL is not an `http://` URL.
+    InvalidUrl(String),
+    Io(io::Error),
+    /// The server answered with this status instead of 200.
+    Status(u16),
+    InvalidResponse,
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidUrl(url) => write!(f, "{url} should be an http:// URL"),
+            Self::Io(error) => write!(f, "Could not fetch the input: {error}"),
+            Self::Status(status) => write!(f, "The server answered with status {status}"),
+            Self::InvalidResponse => write!(f, "The server sent an invalid HTTP response"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Where puzzle inputs are downloaded from and cached to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputSource {
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+}
+
+impl InputSource {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str, cache_dir: PathBuf) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            cache_dir,
+        }
+    }
+
+    /// The source configured in the environment, or `None` if fetching is not configured.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    /// The source configured by the variables `var` looks up.
+    #[must_use]
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let base_url = var("AOC_BASE_URL")?;
+        let session = var("AOC_SESSION")?;
+        let cache_dir = var("AOC_CACHE_DIR")
+            .map(PathBuf::from)
+            .or_else(|| data_dir(&var).map(|data| data.join("inputs")))?;
+
+        Some(Self::new(&base_url, &session, cache_dir))
+    }
+
+    /// Where the puzzle input for `day` in `year` is cached.
+    #[must_use]
+    pub fn cache_path(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(format!("{year}/day{day:02}_puzzle.txt"))
+    }
+
+    /// The puzzle input for `day` in `year`, from the cache or else downloaded into it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not cached and cannot be downloaded, or if the cache
+    /// cannot be written.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        let path = self.cache_path(year, day);
+        if path.exists() {
+            return Ok(fs::read_to_string(path)?);
+        }
+
+        let input = self.download(year, day)?;
+
+        // Write then rename, so that an interrupted download never looks cached.
+        fs::create_dir_all(self.cache_dir.join(year.to_string()))?;
+        let partial = path.with_extension("part");
+        fs::write(&partial, &input)?;
+        fs::rename(partial, path)?;
+
+        Ok(input)
+    }
+
+    fn download(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        self.request(&format!("/{year}/day/{day}/input"), None)
+    }
+
+    /// Sends a request for `path` under the base URL, as a form POST when there is a `form`
+    /// and a GET otherwise, and returns the body of a 200 response.
+    pub(crate) fn request(&self, path: &str, form: Option<&str>) -> Result<String, FetchError> {
+        let address = self
+            .base_url
+            .strip_prefix("http://")
+            .ok_or_else(|| FetchError::InvalidUrl(self.base_url.clone()))?;
+        let (host, base_path) = address
+            .find('/')
+            .map_or((address, ""), |slash| address.split_at(slash));
+        if host.is_empty() {
+            return Err(FetchError::InvalidUrl(self.base_url.clone()));
+        }
+        let socket = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{host}:80")
+        };
+
+        let (method, form_headers) = form.map_or_else(
+            || ("GET", String::new()),
+            |form| {
+                (
+                    "POST",
+                    format!(
+                        "Content-Type: application/x-www-form-urlencoded\r\n\
+                         Content-Length: {}\r\n",
+                        form.len()
+                    ),
+                )
+            },
+        );
+
+        let mut stream = TcpStream::connect(socket)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        write!(
+            stream,
+            "{method} {base_path}{path} HTTP/1.1\r\n\
+             Host: {host}\r\n\
+             Cookie: session={}\r\n\
+             User-Agent: aoc-2023-rust\r\n\
+             {form_headers}\
+             Connection: close\r\n\r\n\
+             {}",
+            self.session,
+            form.unwrap_or_default()
+        )?;
+
+        let mut response = vec![];
+        stream.read_to_end(&mut response)?;
+
+        body(&response)
+    }
+}
+
+/// The directory this crate keeps its data in: `aoc-2023-rust` under `$XDG_DATA_HOME`, or
+/// under `~/.local/share`.
+pub(crate) fn data_dir(var: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    var("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .map(|data| data.join("aoc-2023-rust"))
+}
+
+/// The body of a complete HTTP response, if its status is 200.
+fn body(response: &[u8]) -> Result<String, FetchError> {
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or(FetchError::InvalidResponse)?;
+    let head =
+        std::str::from_utf8(&response[..header_end]).map_err(|_| FetchError::InvalidResponse)?;
+    let body = &response[header_end + 4..];
+
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|status_line| status_line.split(' ').nth(1))
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or(FetchError::InvalidResponse)?;
+    if status != 200 {
+        return Err(FetchError::Status(status));
+    }
+
+    let header = |name: &str| {
+        lines.clone().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    };
+
+    let body =
+        if header("Transfer-Encoding").is_some_and(|value| value.eq_ignore_ascii_case("chunked")) {
+            dechunk(body)?
+        } else if let Some(length) = header("Content-Length") {
+            let length = length
+                .parse::<usize>()
+                .map_err(|_| FetchError::InvalidResponse)?;
+            body.get(..length)
+                .ok_or(FetchError::InvalidResponse)?
+                .to_vec()
+        } else {
+            body.to_vec()
+        };
+
+    String::from_utf8(body).map_err(|_| FetchError::InvalidResponse)
+}
+
+fn dechunk(mut chunks: &[u8]) -> Result<Vec<u8>, FetchError> {
+    let mut body = vec![];
+
+    loop {
+        let line_end = chunks
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .ok_or(FetchError::InvalidResponse)?;
+        let size = std::str::from_utf8(&chunks[..line_end])
+            .ok()
+            .and_then(|line| usize::from_str_radix(line.split(';').next()?.trim(), 16).ok())
+            .ok_or(FetchError::InvalidResponse)?;
+        if size == 0 {
+            return Ok(body);
+        }
+
+        let chunk = chunks
+            .get(line_end + 2..line_end + 2 + size)
+            .ok_or(FetchError::InvalidResponse)?;
+        body.extend_from_slice(chunk);
+        chunks = chunks
+            .get(line_end + 4 + size..)
+            .ok_or(FetchError::InvalidResponse)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+    use std::io::BufReader;
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+
+    use super::*;
+    use crate::{FileLoader, TaskType};
+
+    /// Serves one canned response per connection, sending each request line and cookie back.
+    fn serve(responses: Vec<&'static str>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (requests, received) = mpsc::channel();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let head = (&mut reader)
+                    .lines()
+                    .map_while(Result::ok)
+                    .take_while(|line| !line.is_empty())
+                    .filter(|line| line.starts_with("GET") || line.starts_with("Cookie"))
+                    .collect::<Vec<String>>();
+
+                requests.send(head.join("\n")).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (format!("http://{address}/proxy"), received)
+    }
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn inputs_are_downloaded_once() {
+        let (base_url, requests) = serve(vec![
+            "HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\n1 2 3\nignored",
+        ]);
+        let source = InputSource::new(&base_url, "secret", cache_dir("once"));
+
+        assert_eq!(source.input(2023, 6).unwrap(), "1 2 3\n");
+        assert_eq!(
+            requests.recv().unwrap(),
+            "GET /proxy/2023/day/6/input HTTP/1.1\nCookie: session=secret"
+        );
+
+        // The server only answers once, so this has to come from the cache.
+        assert_eq!(source.input(2023, 6).unwrap(), "1 2 3\n");
+        assert_eq!(
+            fs::read_to_string(source.cache_path(2023, 6)).unwrap(),
+            "1 2 3\n"
+        );
+        fs::remove_dir_all(&source.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn missing_puzzle_inputs_are_fetched_by_the_loader() {
+        let (base_url, requests) = serve(vec!["HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\n1 2\n"]);
+        let source = InputSource::new(&base_url, "secret", cache_dir("loader"));
+        let read =
+            || FileLoader::read_or_fetch(2015, "01", "", TaskType::Puzzle, || Some(source.clone()));
+
+        assert_eq!(read().unwrap(), "1 2\n");
+        assert_eq!(
+            requests.recv().unwrap(),
+            "GET /proxy/2015/day/1/input HTTP/1.1\nCookie: session=secret"
+        );
+
+        // The server only answers once, so this has to come from the cache.
+        assert_eq!(read().unwrap(), "1 2\n");
+        assert_eq!(
+            fs::read_to_string(source.cache_path(2015, 1)).unwrap(),
+            "1 2\n"
+        );
+
+        // Without a source, the missing file is reported as it is.
+        assert_eq!(
+            FileLoader::read_or_fetch(2015, "01", "", TaskType::Puzzle, || None)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+        fs::remove_dir_all(&source.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn chunked_responses_are_joined() {
+        let (base_url, _requests) = serve(vec![
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nTime\r\n6;ext=1\r\n: 7 15\r\n0\r\n\r\n",
+        ]);
+        let source = InputSource::new(&base_url, "secret", cache_dir("chunked"));
+
+        assert_eq!(source.input(2023, 6).unwrap(), "Time: 7 15");
+        fs::remove_dir_all(&source.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn failed_downloads_are_not_cached() {
+        let (base_url, _requests) = serve(vec![
+            "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n",
+            "garbage",
+        ]);
+        let source = InputSource::new(&base_url, "expired", cache_dir("failed"));
+
+        assert!(matches!(
+            source.input(2015, 1),
+            Err(FetchError::Status(400))
+        ));
+        assert!(matches!(
+            source.input(2015, 1),
+            Err(FetchError::InvalidResponse)
+        ));
+        assert!(!source.cache_path(2015, 1).exists());
+        assert!(matches!(
+            InputSource::new("https://example.com", "", cache_dir("https")).input(2015, 1),
+            Err(FetchError::InvalidUrl(_))
+        ));
+    }
+
+    #[test]
+    fn configured_from_variables() {
+        let vars = |pairs: &'static [(&str, &str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| (*value).to_string())
+            }
+        };
+
+        assert_eq!(
+            InputSource::from_vars(vars(&[
+                ("AOC_BASE_URL", "http://localhost:8080/"),
+                ("AOC_SESSION", "secret"),
+                ("HOME", "/home/elf"),
+            ])),
+            Some(InputSource::new(
+                "http://localhost:8080",
+                "secret",
+                PathBuf::from("/home/elf/.local/share/aoc-2023-rust/inputs")
+            ))
+        );
+        assert_eq!(
+            InputSource::from_vars(vars(&[
+                ("AOC_BASE_URL", "http://localhost"),
+                ("AOC_SESSION", "secret"),
+                ("AOC_CACHE_DIR", "/cache"),
+                ("XDG_DATA_HOME", "/data"),
+            ]))
+            .map(|source| source.cache_path(2023, 3)),
+            Some(PathBuf::from("/cache/2023/day03_puzzle.txt"))
+        );
+        assert_eq!(
+            InputSource::from_vars(vars(&[("AOC_BASE_URL", "http://localhost")])),
+            None
+        );
+    }
+}
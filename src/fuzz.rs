@@ -0,0 +1,308 @@
+//! Fuzz targets for every 2023 day's parse-and-solve entry points, and a small mutational fuzzer to
+//! drive them locally.
+//!
+//! A target feeds arbitrary bytes to both parts of a day and only panics if the day does, as
+//! malformed input should come back as an error. Arithmetic overflow panics too, as long as
+//! the fuzzer runs in a build with overflow checks, such as the test profile.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::str;
+
+use crate::generate::Rng;
+use crate::shrink::{self, panic_message};
+use crate::year2023::day01::{self, DigitlessLines, Language, Vocabulary, WordReplacement};
+use crate::year2023::day02::{self, Bag, Duplicates};
+use crate::year2023::day04::{self, Overflow};
+use crate::year2023::{day03, day05, day06, day07, YEAR};
+use crate::{examples, FileLoader, TaskType};
+
+/// Feeds arbitrary bytes to a day's solvers, panicking only if one of them does.
+pub type Target = fn(&[u8]);
+
+/// The fuzz target for `day`, or `None` for a day without one.
+#[must_use]
+pub fn target(day: u8) -> Option<Target> {
+    let target: Target = match day {
+        1 => day01,
+        2 => day02,
+        3 => day03,
+        4 => day04,
+        5 => day05,
+        6 => day06,
+        7 => day07,
+        _ => return None,
+    };
+
+    Some(target)
+}
+
+fn day01(bytes: &[u8]) {
+    let words = WordReplacement::Vocabulary(Vocabulary::builtin(Language::English));
+
+    for word_replacement in [WordReplacement::Disabled, words] {
+        let _ = day01::calibrate(bytes, &word_replacement, &DigitlessLines::Reject);
+        let _ = day01::explain(bytes, &word_replacement, &DigitlessLines::ScoreZero)
+            .map(|report| report.to_string());
+    }
+}
+
+// The remaining days read text, so invalid UTF-8 is turned away before it reaches them.
+
+fn day02(bytes: &[u8]) {
+    let Ok(data) = str::from_utf8(bytes) else {
+        return;
+    };
+
+    let _ = day02::part_one(data, &Bag::standard());
+    let _ = day02::part_two(data);
+    if let Ok(games) = day02::parse(data, &Duplicates::Sum) {
+        let _ = games.analyse(&Bag::standard()).to_json().to_string();
+    }
+}
+
+fn day03(bytes: &[u8]) {
+    if let Ok(data) = str::from_utf8(bytes) {
+        let _ = day03::part_one(data);
+        let _ = day03::part_two(data);
+    }
+}
+
+fn day04(bytes: &[u8]) {
+    if let Ok(data) = str::from_utf8(bytes) {
+        let _ = day04::part_one(data);
+        let _ = day04::part_two(data, &Overflow::Error);
+        let _ = day04::cascade(data, &Overflow::Clamp).map(|cascade| cascade.to_string());
+    }
+}
+
+fn day05(bytes: &[u8]) {
+    if let Ok(data) = str::from_utf8(bytes) {
+        let _ = day05::part_one(data);
+        let _ = day05::part_two(data);
+    }
+}
+
+fn day06(bytes: &[u8]) {
+    if let Ok(data) = str::from_utf8(bytes) {
+        let _ = day06::part_one(data);
+        let _ = day06::part_two(data);
+    }
+}
+
+fn day07(bytes: &[u8]) {
+    if let Ok(data) = str::from_utf8(bytes) {
+        let _ = day07::part_one(data);
+        let _ = day07::part_two(data);
+    }
+}
+
+/// The example inputs for `day`, named ones included, which every fuzzing run starts out
+/// mutating.
+#[must_use]
+pub fn seed_corpus(day: u8) -> Vec<Vec<u8>> {
+    let mut corpus = match day {
+        1 => ["part1", "part2"]
+            .iter()
+            .map(|infix| {
+                FileLoader::load_with_infix(YEAR, "01", infix, TaskType::Example).into_bytes()
+            })
+            .collect(),
+        day => vec![FileLoader::load(YEAR, &format!("{day:02}"), TaskType::Example).into_bytes()],
+    };
+    corpus.extend(
+        examples::examples(YEAR, day)
+            .into_iter()
+            .flatten()
+            .map(|example| example.data.into_bytes()),
+    );
+
+    corpus
+}
+
+/// An input that made a target panic, shrunk as far as it still does.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Crash {
+    pub input: Vec<u8>,
+    pub message: String,
+}
+
+/// Runs `target` on `iterations` mutations of the corpus, returning the first input it
+/// panics on.
+///
+/// Runs are reproducible from `seed`. Panics are expected while fuzzing, so callers may want
+/// to silence the panic hook.
+#[must_use]
+pub fn fuzz(target: Target, corpus: &[Vec<u8>], iterations: u64, seed: u64) -> Option<Crash> {
+    const MAX_POOL: usize = 64;
+    let mut rng = Rng::new(seed);
+    let mut pool = corpus.to_vec();
+    if pool.is_empty() {
+        pool.push(vec![]);
+    }
+
+    for _ in 0..iterations {
+        let mut input = rng.pick(&pool).clone();
+        for _ in 0..rng.between(1, 4) {
+            mutate(&mut rng, &mut input, &pool);
+        }
+
+        if let Err(message) = run(target, &input) {
+            return Some(minimise(target, input, message));
+        }
+
+        // Mutations that survive are mutated further, so that damage can build up.
+        if pool.len() < MAX_POOL && rng.chance(1, 8) {
+            pool.push(input);
+        } else if rng.chance(1, 8) {
+            let index = rng.index(pool.len());
+            pool[index] = input;
+        }
+    }
+
+    None
+}
+
+fn run(target: Target, input: &[u8]) -> Result<(), String> {
+    panic::catch_unwind(AssertUnwindSafe(|| target(input))).map_err(panic_message)
+}
+
+/// Shrinks a crash by lines when it is text, keeping the message from the smallest input.
+fn minimise(target: Target, input: Vec<u8>, message: String) -> Crash {
+    let shrunk = str::from_utf8(&input)
+        .ok()
+        .and_then(|data| shrink::shrink(data, |data| run(target, data.as_bytes()).is_err()));
+
+    match shrunk {
+        Some(data) => Crash {
+            message: run(target, data.as_bytes()).err().unwrap_or(message),
+            input: data.into_bytes(),
+        },
+        None => Crash { input, message },
+    }
+}
+
+/// Fragments that tend to matter to the parsers: separators, and numbers at the edges of the
+/// integer types.
+const TOKENS: [&[u8]; 18] = [
+    b" ",
+    b"\n",
+    b"\n\n",
+    b":",
+    b"|",
+    b";",
+    b",",
+    b"0",
+    b"*",
+    b"J",
+    b"map:",
+    b"4294967295",
+    b"4294967296",
+    b"18446744073709551615",
+    b"18446744073709551616",
+    b"99999999999999999999999999",
+    b"\xe2\x82\xac",
+    b"\xff",
+];
+
+fn mutate(rng: &mut Rng, input: &mut Vec<u8>, pool: &[Vec<u8>]) {
+    let position = rng.index(input.len() + 1);
+
+    match rng.below(5) {
+        0 if position < input.len() => {
+            input[position] = u8::try_from(rng.below(256)).unwrap_or_default();
+        }
+        1 if position < input.len() => {
+            let end = (position + rng.index(8) + 1).min(input.len());
+            input.drain(position..end);
+        }
+        2 if position < input.len() => {
+            let end = (position + rng.index(16) + 1).min(input.len());
+            let copy = input[position..end].to_vec();
+            let at = rng.index(input.len() + 1);
+            input.splice(at..at, copy);
+        }
+        3 => {
+            let other = rng.pick(pool);
+            let start = rng.index(other.len() + 1);
+            let end = (start + rng.index(32)).min(other.len());
+            input.splice(position..position, other[start..end].iter().copied());
+        }
+        _ => {
+            input.splice(position..position, rng.pick(&TOKENS).iter().copied());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn fuzz_day(day: u8) {
+        let iterations = env::var("AOC_FUZZ_ITERATIONS").map_or(300, |value| {
+            value
+                .parse()
+                .expect("AOC_FUZZ_ITERATIONS should be a number")
+        });
+
+        if let Some(crash) = fuzz(target(day).unwrap(), &seed_corpus(day), iterations, 2023) {
+            panic!(
+                "Day {day} panicked with `{}` on:\n{}",
+                crash.message,
+                String::from_utf8_lossy(&crash.input)
+            );
+        }
+    }
+
+    #[test]
+    fn day01_survives_fuzzing() {
+        fuzz_day(1);
+    }
+
+    #[test]
+    fn day02_survives_fuzzing() {
+        fuzz_day(2);
+    }
+
+    #[test]
+    fn day03_survives_fuzzing() {
+        fuzz_day(3);
+    }
+
+    #[test]
+    fn day04_survives_fuzzing() {
+        fuzz_day(4);
+    }
+
+    #[test]
+    fn day05_survives_fuzzing() {
+        fuzz_day(5);
+    }
+
+    #[test]
+    fn day06_survives_fuzzing() {
+        fuzz_day(6);
+    }
+
+    #[test]
+    fn day07_survives_fuzzing() {
+        fuzz_day(7);
+    }
+
+    #[test]
+    fn crashes_are_found_and_shrunk() {
+        fn no_pipes(bytes: &[u8]) {
+            if let Ok(data) = str::from_utf8(bytes) {
+                assert!(!data.contains('|'), "found a pipe");
+            }
+        }
+
+        let corpus = [b"first line\nsecond line\nthird line".to_vec()];
+        let crash = fuzz(no_pipes, &corpus, 10_000, 1).unwrap();
+
+        assert_eq!(crash.message, "found a pipe");
+        assert!(crash.input.contains(&b'|'));
+        assert!(!crash.input.contains(&b'\n'));
+    }
+}
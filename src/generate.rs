@@ -0,0 +1,485 @@
+//! Random, syntactically valid 2023 puzzle inputs for stress-testing the solvers.
+//!
+//! Every generator is deterministic for a given seed, so any input that trips up a solver can be
+//! reproduced from the seed and sizes alone.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// A small, fast, seedable pseudo-random number generator (`SplitMix64`).
+///
+/// It is nowhere near cryptographic quality, but it is plenty for shuffling puzzle inputs and
+/// needs no dependencies.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    /// A value in `0..bound`. `bound` must not be zero.
+    pub const fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// A value in `low..=high`.
+    pub const fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+
+    /// An index into a collection of `length` items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is zero.
+    pub fn index(&mut self, length: usize) -> usize {
+        let length = u64::try_from(length).expect("A usize fits in a u64");
+        usize::try_from(self.below(length)).expect("An index below a usize fits in one")
+    }
+
+    /// True with a probability of `numerator / denominator`.
+    pub const fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+}
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Day 1: calibration lines of letters, digits and, if `spelled`, spelled-out digits.
+///
+/// Every line holds at least one digit, or one spelled-out digit when `spelled` is set.
+#[must_use]
+pub fn calibration(seed: u64, lines: usize, spelled: bool) -> String {
+    let mut rng = Rng::new(seed);
+    let mut data = String::new();
+
+    for _ in 0..lines {
+        let mut line = String::new();
+        let pieces = rng.between(1, 8);
+        let guaranteed = rng.below(pieces);
+
+        for piece in 0..pieces {
+            match rng.below(3) {
+                _ if piece == guaranteed && spelled && rng.chance(1, 2) => {
+                    line.push_str(rng.pick::<&str>(&DIGIT_WORDS));
+                }
+                _ if piece == guaranteed => line.push(digit(rng.between(1, 9))),
+                0 => line.push(digit(rng.below(10))),
+                1 if spelled => line.push_str(rng.pick::<&str>(&DIGIT_WORDS)),
+                _ => {
+                    for _ in 0..rng.between(1, 4) {
+                        line.push(char::from(*rng.pick(LETTERS)));
+                    }
+                }
+            }
+        }
+
+        data.push_str(&line);
+        data.push('\n');
+    }
+
+    data
+}
+
+/// Day 2: games of up to `max_pulls` pulls of red, green and blue cubes, with at most
+/// `max_cubes` of a color in any one pull.
+#[must_use]
+pub fn games(seed: u64, games: usize, max_pulls: u64, max_cubes: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut data = String::new();
+
+    for game_number in 1..=games {
+        let mut pulls = vec![];
+        for _ in 0..rng.between(1, max_pulls.max(1)) {
+            let mut pull = vec![];
+            for color in ["red", "green", "blue"] {
+                if pull.is_empty() && color == "blue" || rng.chance(2, 3) {
+                    pull.push(format!("{} {color}", rng.between(1, max_cubes.max(1))));
+                }
+            }
+
+            // The order of the colors within a pull varies in the puzzle input too.
+            let swap = rng.index(pull.len());
+            pull.swap(0, swap);
+            pulls.push(pull.join(", "));
+        }
+
+        let _ = writeln!(data, "Game {game_number}: {}", pulls.join("; "));
+    }
+
+    data
+}
+
+/// Day 3: a `width` by `height` engine schematic of numbers, symbols and dots.
+#[must_use]
+pub fn schematic(seed: u64, width: usize, height: usize) -> String {
+    const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+    let mut rng = Rng::new(seed);
+    let mut data = String::new();
+
+    for _ in 0..height {
+        let mut row = String::new();
+        while row.len() < width {
+            match rng.below(10) {
+                0..=1 => {
+                    let digits = rng.between(1, 3).min((width - row.len()) as u64);
+                    let smallest = 10u64.pow(u32::try_from(digits).unwrap_or(1) - 1);
+                    let _ = write!(row, "{}", rng.between(smallest, smallest * 10 - 1));
+                    // Numbers on the same row are always kept apart by at least one dot.
+                    if row.len() < width {
+                        row.push('.');
+                    }
+                }
+                2 => row.push(char::from(*rng.pick(SYMBOLS))),
+                _ => row.push('.'),
+            }
+        }
+
+        data.push_str(&row);
+        data.push('\n');
+    }
+
+    data
+}
+
+/// The most scratchcards, originals and copies, that [`scratchcards`] will let a table cascade to.
+pub const MAX_SCRATCHCARDS: u64 = 10_000_000;
+
+/// Day 4: scratchcards with `winning` winning numbers and `drawn` drawn numbers each, all
+/// below 100. A card holds at most 99 distinct numbers, so larger counts are clamped to fit,
+/// winning numbers first.
+///
+/// No card wins copies of cards past the end of the table, and the cascade is kept to at most
+/// [`MAX_SCRATCHCARDS`] cards in total, as copies otherwise multiply exponentially.
+#[must_use]
+pub fn scratchcards(seed: u64, cards: usize, winning: usize, drawn: usize) -> String {
+    const NUMBERS: usize = 99;
+    let winning = winning.min(NUMBERS);
+    let drawn = drawn.min(NUMBERS - winning);
+
+    let mut rng = Rng::new(seed);
+    let mut data = String::new();
+    let width = cards.to_string().len();
+    let mut instances = vec![1_u64; cards];
+    let mut total = cards as u64;
+
+    for card in 1..=cards {
+        let max_matches = winning.min(drawn).min(cards - card);
+        let mut matches = rng.index(max_matches + 1);
+
+        let current = instances[card - 1];
+        let copies = current * matches as u64;
+        if total + copies > MAX_SCRATCHCARDS {
+            matches = 0;
+        } else {
+            total += copies;
+            for following in &mut instances[card..card + matches] {
+                *following += current;
+            }
+        }
+
+        let mut numbers = distinct_numbers(&mut rng, winning + drawn - matches, NUMBERS as u64 + 1);
+        let mut drawn_numbers = numbers.split_off(winning);
+        drawn_numbers.extend_from_slice(&numbers[..matches]);
+        shuffle(&mut rng, &mut drawn_numbers);
+
+        let format = |numbers: &[u64]| {
+            numbers
+                .iter()
+                .map(|number| format!("{number:>2}"))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+
+        let _ = writeln!(
+            data,
+            "Card {card:>width$}: {} | {}",
+            format(&numbers),
+            format(&drawn_numbers)
+        );
+    }
+
+    data
+}
+
+/// Day 5: an almanac with `seed_ranges` seed ranges of at most `max_range` seeds each, and
+/// seven maps of up to `ranges_per_map` ranges over numbers below `max_value`.
+#[must_use]
+pub fn almanac(
+    seed: u64,
+    seed_ranges: usize,
+    ranges_per_map: usize,
+    max_range: u64,
+    max_value: u64,
+) -> String {
+    const CATEGORIES: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+    let mut rng = Rng::new(seed);
+    let max_value = max_value.max(2);
+
+    let seeds = (0..seed_ranges)
+        .map(|_| {
+            let length = rng.between(1, max_range.max(1));
+            format!("{} {length}", rng.below(max_value))
+        })
+        .collect::<Vec<String>>();
+    let mut data = format!("seeds: {}\n", seeds.join(" "));
+
+    for pair in CATEGORIES.windows(2) {
+        let _ = write!(data, "\n{}-to-{} map:\n", pair[0], pair[1]);
+
+        // Source ranges within a map never overlap, so carve them out of sorted cut points.
+        let mut cuts = (0..ranges_per_map.max(1) * 2)
+            .map(|_| rng.below(max_value))
+            .collect::<Vec<u64>>();
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        let mut ranges = cuts
+            .chunks_exact(2)
+            .map(|cut| (cut[0], cut[1] - cut[0] + 1))
+            .collect::<Vec<(u64, u64)>>();
+        if ranges.is_empty() {
+            ranges.push((cuts[0], 1));
+        }
+        shuffle(&mut rng, &mut ranges);
+
+        for (source_start, amount) in ranges {
+            let destination_start = rng.below(max_value);
+            let _ = writeln!(data, "{destination_start} {source_start} {amount}");
+        }
+    }
+
+    data.trim_end().to_string()
+}
+
+/// Day 6: `races` races of at most `max_time` milliseconds, each with a record that can be
+/// beaten.
+#[must_use]
+pub fn races(seed: u64, races: usize, max_time: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let (mut times, mut distances) = (vec![], vec![]);
+
+    for _ in 0..races {
+        let time = rng.between(2, max_time.max(2));
+        let best = (time / 2) * (time - time / 2);
+        times.push(time.to_string());
+        distances.push(rng.below(best).to_string());
+    }
+
+    let width = times
+        .iter()
+        .chain(&distances)
+        .map(String::len)
+        .max()
+        .unwrap_or(0);
+    let pad = |values: &[String]| {
+        values
+            .iter()
+            .map(|value| format!("{value:>width$}"))
+            .collect::<Vec<String>>()
+            .join("  ")
+    };
+
+    format!(
+        "Time:      {}\nDistance:  {}\n",
+        pad(&times),
+        pad(&distances)
+    )
+}
+
+/// Day 7: `hands` distinct camel card hands with bids of up to `max_bid`.
+#[must_use]
+pub fn hands(seed: u64, hands: usize, max_bid: u64) -> String {
+    const CARDS: &[u8] = b"AKQJT98765432";
+    let mut rng = Rng::new(seed);
+    let mut dealt = HashSet::new();
+    let mut data = String::new();
+
+    while dealt.len() < hands.min(CARDS.len().pow(5)) {
+        // Favour a few cards per hand, so that pairs and better turn up as often as they do in
+        // the puzzle input.
+        let palette = (0..rng.between(1, 5))
+            .map(|_| *rng.pick(CARDS))
+            .collect::<Vec<u8>>();
+        let hand = (0..5)
+            .map(|_| char::from(*rng.pick(&palette)))
+            .collect::<String>();
+
+        if dealt.insert(hand.clone()) {
+            let _ = writeln!(data, "{hand} {}", rng.between(1, max_bid.max(1)));
+        }
+    }
+
+    data
+}
+
+/// Generates an input for `day` with one size driving the whole input, using defaults for
+/// every other parameter. Returns `None` for a day without a generator.
+#[must_use]
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<String> {
+    match day {
+        1 => Some(calibration(seed, size, true)),
+        2 => Some(games(seed, size, 6, 20)),
+        3 => Some(schematic(seed, size, size)),
+        4 => Some(scratchcards(seed, size, 5, 8)),
+        5 => Some(almanac(seed, 2, size, 100, 1000)),
+        6 => Some(races(seed, size, 100)),
+        7 => Some(hands(seed, size, 1000)),
+        _ => None,
+    }
+}
+
+fn digit(value: u64) -> char {
+    char::from_digit(u32::try_from(value).unwrap_or(0), 10).expect("A single digit")
+}
+
+fn distinct_numbers(rng: &mut Rng, count: usize, bound: u64) -> Vec<u64> {
+    let mut numbers = (1..bound).collect::<Vec<u64>>();
+    shuffle(rng, &mut numbers);
+    numbers.truncate(count);
+    numbers
+}
+
+/// A Fisher-Yates shuffle.
+fn shuffle<T>(rng: &mut Rng, items: &mut [T]) {
+    for index in (1..items.len()).rev() {
+        items.swap(index, rng.index(index + 1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year2023::day01::{self, DigitlessLines, Language, Vocabulary, WordReplacement};
+    use crate::year2023::day02::{self, Duplicates};
+    use crate::year2023::day04::{self, Overflow};
+
+    #[test]
+    fn generators_are_deterministic() {
+        for day in 1..=7 {
+            assert_eq!(generate(day, 7, 20), generate(day, 7, 20), "day {day}");
+            assert_ne!(generate(day, 7, 20), generate(day, 8, 20), "day {day}");
+        }
+    }
+
+    #[test]
+    fn calibration_lines_always_have_a_digit() {
+        let data = calibration(1, 500, false);
+        let words = WordReplacement::Vocabulary(Vocabulary::builtin(Language::English));
+
+        assert!(day01::calibrate(
+            data.as_bytes(),
+            &WordReplacement::Disabled,
+            &DigitlessLines::Reject
+        )
+        .is_ok());
+        assert!(day01::calibrate(
+            calibration(1, 500, true).as_bytes(),
+            &words,
+            &DigitlessLines::Reject
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn games_parse() {
+        let data = games(2, 300, 6, 20);
+        let games = day02::parse(&data, &Duplicates::Reject).unwrap();
+
+        assert_eq!(games.games().len(), 300);
+        assert_eq!(games.colors().len(), 3);
+    }
+
+    #[test]
+    fn schematic_has_requested_shape() {
+        let data = schematic(3, 40, 25);
+
+        assert_eq!(data.lines().count(), 25);
+        assert!(data.lines().all(|line| line.len() == 40));
+    }
+
+    #[test]
+    fn scratchcards_never_overflow() {
+        let data = scratchcards(4, 200, 10, 25);
+
+        assert!(day04::part_two(&data, &Overflow::Error).is_ok());
+    }
+
+    #[test]
+    fn scratchcard_numbers_are_clamped_to_the_pool() {
+        let data = scratchcards(4, 20, 60, 80);
+
+        for line in data.lines() {
+            let (winning, drawn) = line.split_once(':').unwrap().1.split_once('|').unwrap();
+            assert_eq!(winning.split_whitespace().count(), 60);
+            assert_eq!(drawn.split_whitespace().count(), 39);
+        }
+        assert!(day04::part_two(&data, &Overflow::Error).is_ok());
+    }
+
+    #[test]
+    fn almanac_has_seven_maps() {
+        let data = almanac(5, 3, 4, 50, 1000);
+
+        assert_eq!(data.matches(" map:").count(), 7);
+        assert_eq!(data.lines().next().unwrap().split_whitespace().count(), 7);
+    }
+
+    #[test]
+    fn races_are_winnable() {
+        let data = races(6, 4, 60);
+        let values = data
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .skip(1)
+                    .map(|value| value.parse::<u64>().unwrap())
+                    .collect::<Vec<u64>>()
+            })
+            .collect::<Vec<Vec<u64>>>();
+
+        for (time, distance) in values[0].iter().zip(&values[1]) {
+            assert!((time / 2) * (time - time / 2) > *distance);
+        }
+    }
+
+    #[test]
+    fn hands_are_distinct() {
+        let data = hands(7, 500, 1000);
+        let hands = data
+            .lines()
+            .map(|line| line.split_once(' ').unwrap().0)
+            .collect::<HashSet<&str>>();
+
+        assert_eq!(hands.len(), 500);
+    }
+}
@@ -0,0 +1,449 @@
+use std::fmt::{Display, Formatter, Write};
+
+/// A JSON value, just rich enough for the reports this crate writes and reads back.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    /// A negative whole number. Whole numbers from zero up are [`Json::Unsigned`], so that
+    /// every integer has one representation.
+    Integer(i64),
+    Unsigned(u64),
+    Float(f64),
+    String(String),
+    Array(Vec<Self>),
+    Object(Vec<(String, Self)>),
+}
+
+impl Json {
+    /// Builds an object from its fields, keeping them in the order given.
+    pub fn object<'a>(fields: impl IntoIterator<Item = (&'a str, Self)>) -> Self {
+        Self::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    /// Reads a single JSON value, surrounded by nothing but whitespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns where `text` stops being valid JSON.
+    pub fn parse(text: &str) -> Result<Self, JsonError> {
+        let mut reader = Reader { text, position: 0 };
+        let value = reader.value()?;
+        reader.skip_whitespace();
+
+        match reader.position {
+            end if end == text.len() => Ok(value),
+            position => Err(JsonError { position }),
+        }
+    }
+
+    /// The value of `key`, if this is an object with that key.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Object(fields) => fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::Unsigned(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_array(&self) -> Option<&[Self]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// Why text could not be read as JSON.
+#[derive(Debug, PartialEq, Eq)]
+pub struct JsonError {
+    /// The byte offset of the first thing that is not valid JSON.
+    pub position: usize,
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid JSON at byte {}", self.position)
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// Reads JSON values from `text`, one byte at a time.
+struct Reader<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl Reader<'_> {
+    const fn error(&self) -> JsonError {
+        JsonError {
+            position: self.position,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    /// Consumes `expected` if the text continues with it.
+    fn eat(&mut self, expected: &str) -> bool {
+        let found = self.text[self.position..].starts_with(expected);
+        if found {
+            self.position += expected.len();
+        }
+        found
+    }
+
+    fn value(&mut self) -> Result<Json, JsonError> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(b'n') if self.eat("null") => Ok(Json::Null),
+            Some(b't') if self.eat("true") => Ok(Json::Bool(true)),
+            Some(b'f') if self.eat("false") => Ok(Json::Bool(false)),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b'[') => self.array(),
+            Some(b'{') => self.object(),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => Err(self.error()),
+        }
+    }
+
+    /// Reads the values of a list between `open` and `close`, separated by commas.
+    fn list<T>(
+        &mut self,
+        open: &str,
+        close: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, JsonError>,
+    ) -> Result<Vec<T>, JsonError> {
+        let mut items = vec![];
+        self.eat(open);
+        self.skip_whitespace();
+        if self.eat(close) {
+            return Ok(items);
+        }
+
+        loop {
+            items.push(item(self)?);
+            self.skip_whitespace();
+            if self.eat(close) {
+                return Ok(items);
+            }
+            if !self.eat(",") {
+                return Err(self.error());
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, JsonError> {
+        self.list("[", "]", Self::value).map(Json::Array)
+    }
+
+    fn object(&mut self) -> Result<Json, JsonError> {
+        let fields = self.list("{", "}", |reader| {
+            reader.skip_whitespace();
+            if reader.peek() != Some(b'"') {
+                return Err(reader.error());
+            }
+            let key = reader.string()?;
+            reader.skip_whitespace();
+            if !reader.eat(":") {
+                return Err(reader.error());
+            }
+            Ok((key, reader.value()?))
+        })?;
+
+        Ok(Json::Object(fields))
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        let mut string = String::new();
+        self.eat("\"");
+
+        loop {
+            let char = self.text[self.position..]
+                .chars()
+                .next()
+                .ok_or_else(|| self.error())?;
+            self.position += char.len_utf8();
+
+            match char {
+                '"' => return Ok(string),
+                '\\' => {
+                    let escape = self.peek().ok_or_else(|| self.error())?;
+                    self.position += 1;
+                    string.push(match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode_escape()?,
+                        _ => return Err(self.error()),
+                    });
+                }
+                char if char.is_control() => return Err(self.error()),
+                char => string.push(char),
+            }
+        }
+    }
+
+    /// Reads the four hex digits of a `\u` escape, and of its low surrogate if it needs one.
+    fn unicode_escape(&mut self) -> Result<char, JsonError> {
+        let mut units = vec![self.hex()?];
+        if (0xd800..0xdc00).contains(&units[0]) && self.eat("\\u") {
+            units.push(self.hex()?);
+        }
+
+        char::decode_utf16(units)
+            .next()
+            .and_then(Result::ok)
+            .ok_or_else(|| self.error())
+    }
+
+    fn hex(&mut self) -> Result<u16, JsonError> {
+        let digits = self
+            .text
+            .get(self.position..self.position + 4)
+            .ok_or_else(|| self.error())?;
+        let unit = u16::from_str_radix(digits, 16).map_err(|_| self.error())?;
+        self.position += 4;
+        Ok(unit)
+    }
+
+    fn number(&mut self) -> Result<Json, JsonError> {
+        let start = self.position;
+        let length = self.text[start..]
+            .find(|char: char| !matches!(char, '-' | '+' | '.' | 'e' | 'E' | '0'..='9'))
+            .unwrap_or(self.text.len() - start);
+        let number = &self.text[start..start + length];
+
+        let value = if number.contains(['.', 'e', 'E']) {
+            number.parse::<f64>().ok().map(Json::Float)
+        } else if number.starts_with('-') {
+            number.parse::<i64>().ok().map(Json::from)
+        } else {
+            number.parse::<u64>().ok().map(Json::Unsigned)
+        };
+
+        let value = value
+            .or_else(|| number.parse::<f64>().ok().map(Json::Float))
+            .ok_or_else(|| self.error())?;
+        self.position += length;
+        Ok(value)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+macro_rules! json_from_unsigned {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Json {
+                fn from(value: $integer) -> Self {
+                    Self::Unsigned(u64::from(value))
+                }
+            }
+        )*
+    };
+}
+
+json_from_unsigned!(u8, u16, u32, u64);
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        // A usize is at most 64 bits wide on every platform Rust supports.
+        u64::try_from(value).map_or(Self::Null, Self::Unsigned)
+    }
+}
+
+impl From<i64> for Json {
+    fn from(value: i64) -> Self {
+        u64::try_from(value).map_or(Self::Integer(value), Self::Unsigned)
+    }
+}
+
+impl<T: Into<Self>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+impl<T: Into<Self>> From<Vec<T>> for Json {
+    fn from(value: Vec<T>) -> Self {
+        Self::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Unsigned(value) => write!(f, "{value}"),
+            // JSON has no representation for NaN or the infinities.
+            Self::Float(value) if !value.is_finite() => write!(f, "null"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::String(value) => write_string(f, value),
+            Self::Array(values) => {
+                f.write_char('[')?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            }
+            Self::Object(fields) => {
+                f.write_char('{')?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut Formatter<'_>, value: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for char in value.chars() {
+        match char {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            char if char.is_control() => write!(f, "\\u{:04x}", u32::from(char))?,
+            char => f.write_char(char)?,
+        }
+    }
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_nested_values() {
+        let json = Json::object([
+            ("name", "say \"hi\"\n".into()),
+            ("count", 3u32.into()),
+            ("ratio", 0.5.into()),
+            ("missing", Option::<u32>::None.into()),
+            ("items", vec![true, false].into()),
+        ]);
+
+        assert_eq!(
+            json.to_string(),
+            r#"{"name":"say \"hi\"\n","count":3,"ratio":0.5,"missing":null,"items":[true,false]}"#
+        );
+    }
+
+    #[test]
+    fn writes_large_answers_exactly() {
+        assert_eq!(Json::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Json::from(-3i64).to_string(), "-3");
+        assert_eq!(Json::from(3i64), Json::from(3u8));
+    }
+
+    #[test]
+    fn reads_back_what_it_writes() {
+        let json = Json::object([
+            ("name", "say \"hi\"\n\u{1}é".into()),
+            ("answer", u64::MAX.into()),
+            ("below", (-3i64).into()),
+            ("ratio", 0.5.into()),
+            ("missing", Option::<u32>::None.into()),
+            ("items", vec![true, false].into()),
+            ("empty", Json::Object(vec![])),
+        ]);
+
+        let read = Json::parse(&format!(" {json}\n")).unwrap();
+        assert_eq!(read, json);
+        assert_eq!(read.get("answer").and_then(Json::as_u64), Some(u64::MAX));
+        assert_eq!(
+            read.get("items")
+                .and_then(Json::as_array)
+                .map(<[Json]>::len),
+            Some(2)
+        );
+        assert_eq!(
+            Json::parse(r#"["\ud83c\udf84", "\/"]"#),
+            Ok(Json::Array(vec!["🎄".into(), "/".into()]))
+        );
+    }
+
+    #[test]
+    fn invalid_json_is_an_error() {
+        assert_eq!(Json::parse(r#"{"a" 1}"#), Err(JsonError { position: 5 }));
+        assert_eq!(Json::parse("[1, 2"), Err(JsonError { position: 5 }));
+        assert_eq!(Json::parse("nul"), Err(JsonError { position: 0 }));
+        assert_eq!(Json::parse("1 2"), Err(JsonError { position: 2 }));
+        assert_eq!(Json::parse("\"\\x\""), Err(JsonError { position: 3 }));
+    }
+}
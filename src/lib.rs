@@ -1,15 +1,26 @@
 use std::fmt::Display;
-use std::fs;
+use std::io;
 
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
+use fetch::InputSource;
 
-#[derive(Debug)]
+pub mod answers;
+#[cfg(test)]
+mod differential;
+pub mod embedded;
+pub mod examples;
+pub mod extract;
+pub mod fetch;
+pub mod fuzz;
+pub mod generate;
+pub mod json;
+pub mod report;
+pub mod shrink;
+pub mod solvers;
+pub mod submit;
+pub mod watch;
+pub mod year2023;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Part {
     PartOne,
     PartTwo,
@@ -23,33 +34,64 @@ pub trait DayRunner {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TaskType {
     Example,
     Puzzle,
 }
 pub struct FileLoader;
 impl FileLoader {
-    fn load(day: &str, task_type: &TaskType) -> String {
-        Self::load_data(day, "", task_type)
+    fn load(year: u16, day: &str, task_type: TaskType) -> String {
+        Self::load_data(year, day, "", task_type)
+    }
+
+    fn load_with_infix(year: u16, day: &str, infix: &str, task_type: TaskType) -> String {
+        Self::load_data(year, day, infix, task_type)
+    }
+
+    fn load_data(year: u16, day: &str, infix: &str, task_type: TaskType) -> String {
+        Self::read(year, day, infix, task_type).unwrap()
     }
 
-    fn load_with_infix(day: &str, infix: &str, task_type: &TaskType) -> String {
-        Self::load_data(day, infix, task_type)
+    /// Reads an input from `<year>/dayNN_*.txt`, leaving a missing or unreadable file for the
+    /// caller to deal with.
+    ///
+    /// Embedded example inputs are read before those on disk, and a missing puzzle input is
+    /// fetched instead, when fetching is configured.
+    fn read(year: u16, day: &str, infix: &str, task_type: TaskType) -> io::Result<String> {
+        Self::read_or_fetch(year, day, infix, task_type, InputSource::from_env)
     }
 
-    fn load_data(day: &str, infix: &str, task_type: &TaskType) -> String {
+    /// Reads an input as [`FileLoader::read`] does, fetching a missing puzzle input from the
+    /// source that `source` gives, if it gives one.
+    fn read_or_fetch(
+        year: u16,
+        day: &str,
+        infix: &str,
+        task_type: TaskType,
+        source: impl FnOnce() -> Option<InputSource>,
+    ) -> io::Result<String> {
         let file_name_ending = match task_type {
             TaskType::Example => "example",
             TaskType::Puzzle => "puzzle",
         };
 
-        let file_path = match infix {
-            "" => format!("src/inputs/day{day}_{file_name_ending}.txt"),
-            infix_value => {
-                format!("src/inputs/day{day}_{infix_value}_{file_name_ending}.txt")
-            }
+        let file_name = match infix {
+            "" => format!("{year}/day{day}_{file_name_ending}.txt"),
+            infix_value => format!("{year}/day{day}_{infix_value}_{file_name_ending}.txt"),
         };
 
-        fs::read_to_string(file_path).unwrap()
+        match embedded::read(&file_name) {
+            Err(error)
+                if error.kind() == io::ErrorKind::NotFound && task_type == TaskType::Puzzle =>
+            {
+                let (Some(source), Ok(day)) = (source(), day.parse::<u8>()) else {
+                    return Err(error);
+                };
+
+                source.input(year, day).map_err(io::Error::other)
+            }
+            result => result,
+        }
     }
 }
@@ -1,25 +1,533 @@
-use aoc_2023_rust::day01::Day01;
-use aoc_2023_rust::day02::Day02;
-use aoc_2023_rust::day03::Day03;
-use aoc_2023_rust::day04::Day04;
-use aoc_2023_rust::day05::Day05;
-use aoc_2023_rust::day06::Day06;
-use aoc_2023_rust::day07::Day07;
-use aoc_2023_rust::{DayRunner, Part};
-
-fn main() {
-    Day01::run(Part::PartOne);
-    Day01::run(Part::PartTwo);
-    Day02::run(Part::PartOne);
-    Day02::run(Part::PartTwo);
-    Day03::run(Part::PartOne);
-    Day03::run(Part::PartTwo);
-    Day04::run(Part::PartOne);
-    Day04::run(Part::PartTwo);
-    Day05::run(Part::PartOne);
-    // Day05::run(Part::PartTwo);
-    Day06::run(Part::PartOne);
-    Day06::run(Part::PartTwo);
-    Day07::run(Part::PartOne);
-    Day07::run(Part::PartTwo);
+use std::env;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::panic;
+use std::path::Path;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use aoc_2023_rust::fetch::InputSource;
+use aoc_2023_rust::report::{RunReport, RunResult};
+use aoc_2023_rust::shrink::{self, Failure};
+use aoc_2023_rust::submit::{self, AnswerStore, Verdict};
+use aoc_2023_rust::watch::{self, Answers, Snapshot};
+use aoc_2023_rust::year2023::day01::{self, DigitlessLines, Language, Vocabulary, WordReplacement};
+use aoc_2023_rust::year2023::day02::{self, Bag, Duplicates};
+use aoc_2023_rust::year2023::day04::{self, Overflow};
+use aoc_2023_rust::{extract, fuzz, generate, solvers, year2023};
+use aoc_2023_rust::{Part, TaskType};
+
+const USAGE: &str = "Usage:
+    aoc-2023-rust [run options]
+    aoc-2023-rust cascade [--example] [--clamp]
+    aoc-2023-rust games [--example] [--bag <color=count,...> | --bag-file <file>] [--duplicates <sum|reject>]
+    aoc-2023-rust analyse-games [games options] [--format <table|json>]
+    aoc-2023-rust calibrate <file> [--language <en|fr|de|es> | --vocabulary <file>] [--zero] [--ignore-case] [--reversed] [--lenient <skip|zero>] [--explain]
+    aoc-2023-rust generate --day <1-7> [--seed <n>] [--size <n>]
+    aoc-2023-rust shrink <file> [--year <year>] --day <1-7> --part <1|2> [--panics | --expect <answer> | --reference] [--output <file>]
+    aoc-2023-rust run [--year <year>] [--all | --day <1-7>] [--example] [--jobs <n>] [--json <file>] [--junit <file>]
+    aoc-2023-rust fuzz --day <1-7> [--iterations <n>] [--seed <n>] [--output <file>]
+    aoc-2023-rust watch [--year <year>] --day <1-7> [--interval <milliseconds>]
+    aoc-2023-rust submit [--year <year>] --day <1-7> --part <1|2> [--answer <answer>]
+    aoc-2023-rust extract <description.html> [--year <year>] --day <n> [--force]";
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    let result = match args.first().map(String::as_str) {
+        None => run(&args),
+        Some("cascade") => cascade(&args[1..]),
+        Some("calibrate") => calibrate(&args[1..]),
+        Some("games") => games(&args[1..]),
+        Some("analyse-games") => analyse_games(&args[1..]),
+        Some("generate") => generate(&args[1..]),
+        Some("shrink") => shrink(&args[1..]),
+        Some("run") => run(&args[1..]),
+        Some(flag) if flag.starts_with("--") => run(&args),
+        Some("fuzz") => fuzz(&args[1..]),
+        Some("watch") => watch(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("extract") => extract(&args[1..]),
+        Some(command) => Err(format!("Unknown command: {command}\n{USAGE}")),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn cascade(args: &[String]) -> Result<(), String> {
+    only_year(args, "cascade", year2023::YEAR)?;
+    let overflow = if has_flag(args, "--clamp") {
+        Overflow::Clamp
+    } else {
+        Overflow::Error
+    };
+
+    let data = input(4, args)?;
+    let cascade = day04::cascade(&data, &overflow).map_err(|error| error.to_string())?;
+    print!("{cascade}");
+    Ok(())
+}
+
+fn calibrate(args: &[String]) -> Result<(), String> {
+    only_year(args, "calibrate", year2023::YEAR)?;
+    let path = args
+        .first()
+        .filter(|arg| !arg.starts_with("--"))
+        .ok_or_else(|| format!("calibrate needs a file\n{USAGE}"))?;
+
+    let vocabulary = match (option(args, "--language"), option(args, "--vocabulary")) {
+        (Some(_), Some(_)) => return Err("Choose either --language or --vocabulary".to_string()),
+        (Some(language), None) => Some(Vocabulary::builtin(language.parse::<Language>()?)),
+        (None, Some(path)) => Some(Vocabulary::load(path).map_err(|error| error.to_string())?),
+        (None, None) => None,
+    };
+
+    let word_replacement = vocabulary.map_or(WordReplacement::Disabled, |mut vocabulary| {
+        if has_flag(args, "--zero") {
+            vocabulary = vocabulary.with_zero();
+        }
+        if has_flag(args, "--ignore-case") {
+            vocabulary = vocabulary.case_insensitive();
+        }
+        if has_flag(args, "--reversed") {
+            vocabulary = vocabulary.with_reversed();
+        }
+        WordReplacement::Vocabulary(vocabulary)
+    });
+
+    let digitless = match option(args, "--lenient") {
+        None => DigitlessLines::Reject,
+        Some("skip") => DigitlessLines::Skip,
+        Some("zero") => DigitlessLines::ScoreZero,
+        Some(other) => return Err(format!("--lenient should be skip or zero, found {other}")),
+    };
+
+    let file = File::open(path).map_err(|error| format!("Could not open {path}: {error}"))?;
+    let reader = BufReader::new(file);
+
+    if has_flag(args, "--explain") {
+        let report = day01::explain(reader, &word_replacement, &digitless)
+            .map_err(|error| error.to_string())?;
+        print!("{report}");
+    } else {
+        let total = day01::calibrate(reader, &word_replacement, &digitless)
+            .map_err(|error| error.to_string())?;
+        println!("{total}");
+    }
+
+    Ok(())
+}
+
+fn games(args: &[String]) -> Result<(), String> {
+    only_year(args, "games", year2023::YEAR)?;
+    let (bag, duplicates) = game_options(args)?;
+
+    let data = input(2, args)?;
+    let games = day02::parse(&data, &duplicates).map_err(|error| error.to_string())?;
+    let possible = games.possible_games(&bag);
+
+    println!("Bag: {bag}");
+    println!(
+        "Possible games: {}",
+        possible
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    println!(
+        "Sum of possible game numbers: {}",
+        possible.iter().map(|game| u64::from(*game)).sum::<u64>()
+    );
+    for game in games.games() {
+        println!(
+            "Game {} minimal bag: {} (power {})",
+            game.game_number,
+            games.minimal_bag(game),
+            games.power(game)
+        );
+    }
+    println!(
+        "Sum of powers: {}",
+        games
+            .games()
+            .iter()
+            .map(|game| games.power(game))
+            .sum::<u64>()
+    );
+
+    Ok(())
+}
+
+fn analyse_games(args: &[String]) -> Result<(), String> {
+    only_year(args, "analyse-games", year2023::YEAR)?;
+    let (bag, duplicates) = game_options(args)?;
+
+    let data = input(2, args)?;
+    let games = day02::parse(&data, &duplicates).map_err(|error| error.to_string())?;
+    let analysis = games.analyse(&bag);
+
+    match option(args, "--format") {
+        None | Some("table") => print!("{analysis}"),
+        Some("json") => println!("{}", analysis.to_json()),
+        Some(other) => return Err(format!("--format should be table or json, found {other}")),
+    }
+
+    Ok(())
+}
+
+fn generate(args: &[String]) -> Result<(), String> {
+    only_year(args, "generate", year2023::YEAR)?;
+    let day = day(args)?;
+    let seed = number_option(args, "--seed", 2023)?;
+    let size = number_option(args, "--size", 10)?;
+
+    let data = generate::generate(day, seed, size)
+        .ok_or_else(|| format!("There is no generator for day {day}"))?;
+    print!("{data}");
+    Ok(())
+}
+
+fn shrink(args: &[String]) -> Result<(), String> {
+    let path = args
+        .first()
+        .filter(|arg| !arg.starts_with("--"))
+        .ok_or_else(|| format!("shrink needs a file\n{USAGE}"))?;
+    let year = year(args)?;
+    let day = day(args)?;
+    let part = part(args)?;
+    let solver = solvers::solver(year, day, &part)
+        .ok_or_else(|| format!("Day {day} of {year} is not solved"))?;
+
+    let failure = match (
+        has_flag(args, "--panics"),
+        option(args, "--expect"),
+        has_flag(args, "--reference"),
+    ) {
+        (true, None, false) => Failure::Panics,
+        (false, Some(answer), false) => Failure::Mismatch(
+            answer
+                .parse()
+                .map_err(|error| format!("--expect should be a number: {error}"))?,
+        ),
+        (false, None, true) => Failure::Reference(
+            solvers::reference(year, day, &part)
+                .ok_or_else(|| format!("Day {day} has no reference solver for that part"))?,
+        ),
+        _ => return Err("Choose one of --panics, --expect or --reference".to_string()),
+    };
+
+    let data =
+        fs::read_to_string(path).map_err(|error| format!("Could not read {path}: {error}"))?;
+
+    // Every panic the shrinker provokes is expected, so keep them off the terminal.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let shrunk = shrink::shrink(&data, |data| failure.reproduces(solver, data));
+    panic::set_hook(hook);
+
+    let shrunk = shrunk.ok_or_else(|| format!("The solver does not fail on {path}"))?;
+    match option(args, "--output") {
+        None => println!("{shrunk}"),
+        Some(output) => fs::write(output, format!("{shrunk}\n"))
+            .map_err(|error| format!("Could not write {output}: {error}"))?,
+    }
+
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let year = year(args)?;
+    let days = run_days(args, year)?;
+
+    let report = match number_option(args, "--jobs", 1)? {
+        1 => RunReport::run(year, days, &task_type(args)),
+        jobs => RunReport::run_parallel(year, days, &task_type(args), jobs),
+    };
+
+    println!("{report}");
+    println!("{}", report.summary());
+
+    if let Some(output) = option(args, "--json") {
+        fs::write(output, format!("{}\n", report.to_json()))
+            .map_err(|error| format!("Could not write {output}: {error}"))?;
+    }
+    if let Some(output) = option(args, "--junit") {
+        fs::write(output, report.to_junit())
+            .map_err(|error| format!("Could not write {output}: {error}"))?;
+    }
+
+    let failed = report
+        .results
+        .iter()
+        .filter(|result| !result.passed())
+        .count();
+    if failed > 0 {
+        return Err(format!("{failed} of {} parts failed", report.results.len()));
+    }
+
+    Ok(())
+}
+
+/// The days `run` runs: the one given with `--day`, or every solved day of `year` with `--all` or
+/// without either flag.
+fn run_days(args: &[String], year: u16) -> Result<Vec<u8>, String> {
+    let days = match (has_flag(args, "--all"), option(args, "--day")) {
+        (true, Some(_)) => return Err("Choose either --all or --day".to_string()),
+        (false, Some(_)) => vec![day(args)?],
+        (_, None) => solvers::days(year).collect(),
+    };
+    if days.is_empty() {
+        return Err(format!("There are no solutions for {year}"));
+    }
+
+    Ok(days)
+}
+
+fn fuzz(args: &[String]) -> Result<(), String> {
+    only_year(args, "fuzz", year2023::YEAR)?;
+    let day = day(args)?;
+    let target =
+        fuzz::target(day).ok_or_else(|| format!("There is no fuzz target for day {day}"))?;
+    let iterations = number_option(args, "--iterations", 100_000)?;
+    let seed = number_option(args, "--seed", 2023)?;
+
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let crash = fuzz::fuzz(target, &fuzz::seed_corpus(day), iterations, seed);
+    panic::set_hook(hook);
+
+    let Some(crash) = crash else {
+        println!("Day {day} survived {iterations} inputs");
+        return Ok(());
+    };
+
+    match option(args, "--output") {
+        None => println!("{}", String::from_utf8_lossy(&crash.input)),
+        Some(output) => fs::write(output, &crash.input)
+            .map_err(|error| format!("Could not write {output}: {error}"))?,
+    }
+
+    Err(format!("Day {day} panicked: {}", crash.message))
+}
+
+fn watch(args: &[String]) -> Result<(), String> {
+    let year = year(args)?;
+    let day = day(args)?;
+    let interval = Duration::from_millis(number_option(args, "--interval", 500)?);
+    let root = Path::new(".");
+    let mut snapshot = Snapshot::take(root, year, day);
+    let mut answers = Answers::default();
+
+    loop {
+        match watch::rebuild_and_run(year, day) {
+            Ok(current) => {
+                print!("{}", watch::diff(&answers, &current));
+                answers = current;
+            }
+            Err(error) => eprintln!("{error}"),
+        }
+        println!(
+            "Watching {} files for day {day} of {year}...",
+            snapshot.len()
+        );
+
+        loop {
+            thread::sleep(interval);
+            let current = Snapshot::take(root, year, day);
+            if current != snapshot {
+                snapshot = current;
+                break;
+            }
+        }
+    }
+}
+
+fn submit(args: &[String]) -> Result<(), String> {
+    let year = year(args)?;
+    let day = day(args)?;
+    let part = part(args)?;
+    let source =
+        InputSource::from_env().ok_or("Set AOC_BASE_URL and AOC_SESSION to submit answers")?;
+    let mut store = AnswerStore::from_env()
+        .map_err(|error| format!("Could not read the answers store: {error}"))?
+        .ok_or("Set AOC_ANSWERS_FILE or HOME to keep an answers store")?;
+
+    let answer = if let Some(answer) = option(args, "--answer") {
+        answer
+            .parse::<u64>()
+            .map_err(|error| format!("--answer should be a number: {error}"))?
+    } else {
+        let result = RunResult::run(year, day, &part, &TaskType::Puzzle);
+        result.answer.ok_or_else(|| result.to_string())?
+    };
+
+    let verdict = submit::submit(&source, &mut store, year, day, &part, answer)
+        .map_err(|error| error.to_string())?;
+    println!("{year} Day{day:02} {part:?} {answer}: {verdict}");
+
+    match verdict {
+        Verdict::Correct => Ok(()),
+        _ => Err(format!("{answer} was not accepted")),
+    }
+}
+
+fn extract(args: &[String]) -> Result<(), String> {
+    let path = args
+        .first()
+        .filter(|arg| !arg.starts_with("--"))
+        .ok_or_else(|| format!("extract needs a saved puzzle description\n{USAGE}"))?;
+    let year = year(args)?;
+    let day = day(args)?;
+
+    let html =
+        fs::read_to_string(path).map_err(|error| format!("Could not read {path}: {error}"))?;
+    let files = extract::extract(&html).files(day);
+    if files.is_empty() {
+        return Err(format!("{path} has no examples or answers in it"));
+    }
+
+    let directory = Path::new("src/inputs").join(year.to_string());
+    let files = files
+        .into_iter()
+        .map(|(name, contents)| (directory.join(name), contents))
+        .collect::<Vec<_>>();
+    if !has_flag(args, "--force") {
+        if let Some((output, _)) = files.iter().find(|(output, _)| output.exists()) {
+            return Err(format!(
+                "{} already exists, use --force to overwrite it",
+                output.display()
+            ));
+        }
+    }
+
+    fs::create_dir_all(&directory)
+        .map_err(|error| format!("Could not create {}: {error}", directory.display()))?;
+    for (output, contents) in files {
+        fs::write(&output, contents)
+            .map_err(|error| format!("Could not write {}: {error}", output.display()))?;
+        println!("Wrote {}", output.display());
+    }
+
+    Ok(())
+}
+
+fn game_options(args: &[String]) -> Result<(Bag, Duplicates), String> {
+    let bag = match (option(args, "--bag"), option(args, "--bag-file")) {
+        (Some(_), Some(_)) => return Err("Choose either --bag or --bag-file".to_string()),
+        (Some(spec), None) => Bag::parse(spec),
+        (None, Some(path)) => Bag::load(path),
+        (None, None) => Ok(Bag::standard()),
+    }
+    .map_err(|error| error.to_string())?;
+
+    let duplicates = match option(args, "--duplicates") {
+        None | Some("reject") => Duplicates::Reject,
+        Some("sum") => Duplicates::Sum,
+        Some(other) => {
+            return Err(format!(
+                "--duplicates should be sum or reject, found {other}"
+            ))
+        }
+    };
+
+    Ok((bag, duplicates))
+}
+
+fn year(args: &[String]) -> Result<u16, String> {
+    number_option(args, "--year", solvers::DEFAULT_YEAR)
+}
+
+/// Checks that `--year`, if it is given, names `supported`: the only year `command` has tools
+/// for.
+fn only_year(args: &[String], command: &str, supported: u16) -> Result<(), String> {
+    match year(args)? {
+        _ if option(args, "--year").is_none() => Ok(()),
+        year if year == supported => Ok(()),
+        year => Err(format!("{command} only works on {supported}, not {year}")),
+    }
+}
+
+fn day(args: &[String]) -> Result<u8, String> {
+    option(args, "--day")
+        .ok_or_else(|| format!("Missing --day\n{USAGE}"))?
+        .parse::<u8>()
+        .map_err(|error| format!("--day should be a day number: {error}"))
+}
+
+fn part(args: &[String]) -> Result<Part, String> {
+    match option(args, "--part") {
+        Some("1") => Ok(Part::PartOne),
+        Some("2") => Ok(Part::PartTwo),
+        _ => Err(format!("Missing --part of 1 or 2\n{USAGE}")),
+    }
+}
+
+fn number_option<T: FromStr>(args: &[String], name: &str, default: T) -> Result<T, String>
+where
+    T::Err: Display,
+{
+    option(args, name).map_or(Ok(default), |value| {
+        value
+            .parse::<T>()
+            .map_err(|error| format!("{name} should be a number: {error}"))
+    })
+}
+
+/// Reads the 2023 example or puzzle input for `day`, for the commands that explore one day.
+fn input(day: u8, args: &[String]) -> Result<String, String> {
+    solvers::input(year2023::YEAR, day, &Part::PartOne, &task_type(args))
+        .map_err(|error| format!("Could not read the input: {error}"))
+}
+
+fn task_type(args: &[String]) -> TaskType {
+    if has_flag(args, "--example") {
+        TaskType::Example
+    } else {
+        TaskType::Puzzle
+    }
+}
+
+fn has_flag(args: &[String], flag: &str) -> bool {
+    args.iter().any(|arg| arg == flag)
+}
+
+fn option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn run_takes_all_days_or_one() {
+        assert_eq!(run_days(&args("--all"), 2023), Ok((1..=7).collect()));
+        assert_eq!(run_days(&args(""), 2023), Ok((1..=7).collect()));
+        assert_eq!(run_days(&args("--day 3"), 2023), Ok(vec![3]));
+        assert_eq!(
+            run_days(&args("--all --day 3"), 2023),
+            Err("Choose either --all or --day".to_string())
+        );
+        assert_eq!(
+            run_days(&args("--all"), 2015),
+            Err("There are no solutions for 2015".to_string())
+        );
+    }
 }
@@ -0,0 +1,642 @@
+//! Structured results of running the solvers, for writing out as JSON or `JUnit` XML.
+//!
+//! Every part runs in isolation: a panic in one is caught and reported, with where it was
+//! raised, and the run carries on with the rest.
+
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Formatter, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, Once, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::json::Json;
+use crate::shrink::panic_message;
+use crate::{answers, solvers, Part, TaskType};
+
+/// How an answer compares with the known answer for its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verification {
+    Correct,
+    Wrong {
+        expected: u64,
+    },
+    /// There is no answer, or no known answer to compare it with.
+    Unverified,
+}
+
+/// Why a part did not produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    Unsolved,
+    InputMissing(String),
+    Parse(String),
+    Panicked {
+        message: String,
+        /// The file, line and column the panic was raised at.
+        location: Option<String>,
+    },
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsolved => write!(f, "This day is not solved yet"),
+            Self::InputMissing(error) => write!(f, "Could not read the input: {error}"),
+            Self::Parse(error) => write!(f, "Could not parse the input: {error}"),
+            Self::Panicked {
+                message,
+                location: Some(location),
+            } => write!(f, "Panicked at {location}: {message}"),
+            Self::Panicked {
+                message,
+                location: None,
+            } => write!(f, "Panicked: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+/// A one-word summary of how a part went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// There is an answer, and it is not known to be wrong.
+    Ok,
+    WrongAnswer,
+    Unsolved,
+    InputMissing,
+    ParseError,
+    Panicked,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            Self::Ok => "ok",
+            Self::WrongAnswer => "wrong answer",
+            Self::Unsolved => "unsolved",
+            Self::InputMissing => "input missing",
+            Self::ParseError => "parse error",
+            Self::Panicked => "panicked",
+        };
+
+        write!(f, "{status}")
+    }
+}
+
+/// The outcome of solving one part of one day.
+#[derive(Debug)]
+pub struct RunResult {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub task_type: TaskType,
+    pub answer: Option<u64>,
+    /// Missing for a day that only parses as it solves, or that never got as far as parsing.
+    pub parse_time: Option<Duration>,
+    /// The time spent solving, not counting the parse time.
+    pub solve_time: Option<Duration>,
+    pub verification: Verification,
+    pub error: Option<RunError>,
+}
+
+impl RunResult {
+    /// Loads, parses and solves one part of a day, turning a panic into an error.
+    #[must_use]
+    pub fn run(year: u16, day: u8, part: &Part, task_type: &TaskType) -> Self {
+        let mut result = Self {
+            year,
+            day,
+            part: *part,
+            task_type: *task_type,
+            answer: None,
+            parse_time: None,
+            solve_time: None,
+            verification: Verification::Unverified,
+            error: None,
+        };
+
+        let Some(solver) = solvers::solver(year, day, part) else {
+            result.error = Some(RunError::Unsolved);
+            return result;
+        };
+        let data = match solvers::input(year, day, part, task_type) {
+            Ok(data) => data,
+            Err(error) => {
+                result.error = Some(RunError::InputMissing(error.to_string()));
+                return result;
+            }
+        };
+
+        if let Some(parser) = solvers::parser(year, day) {
+            let start = Instant::now();
+            let parsed = isolate(|| parser(&data));
+            result.parse_time = Some(start.elapsed());
+
+            match parsed {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => {
+                    result.error = Some(RunError::Parse(error));
+                    return result;
+                }
+                Err(error) => {
+                    result.error = Some(error);
+                    return result;
+                }
+            }
+        }
+
+        // Solvers parse their own input, so take the time already spent parsing back off.
+        let start = Instant::now();
+        let answer = isolate(|| solver(&data));
+        result.solve_time = Some(
+            start
+                .elapsed()
+                .saturating_sub(result.parse_time.unwrap_or_default()),
+        );
+
+        match answer {
+            Ok(answer) => {
+                result.answer = Some(answer);
+                result.verification = match answers::expected(year, day, part, task_type) {
+                    Some(expected) if expected == answer => Verification::Correct,
+                    Some(expected) => Verification::Wrong { expected },
+                    None => Verification::Unverified,
+                };
+            }
+            Err(error) => result.error = Some(error),
+        }
+
+        result
+    }
+
+    #[must_use]
+    pub const fn status(&self) -> Status {
+        match (&self.error, self.verification) {
+            (None, Verification::Wrong { .. }) => Status::WrongAnswer,
+            (None, _) => Status::Ok,
+            (Some(RunError::Unsolved), _) => Status::Unsolved,
+            (Some(RunError::InputMissing(_)), _) => Status::InputMissing,
+            (Some(RunError::Parse(_)), _) => Status::ParseError,
+            (Some(RunError::Panicked { .. }), _) => Status::Panicked,
+        }
+    }
+
+    /// Whether the part produced an answer that is not known to be wrong.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.status() == Status::Ok
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> Json {
+        let (verification, expected) = match self.verification {
+            Verification::Correct => ("correct", self.answer),
+            Verification::Wrong { expected } => ("wrong", Some(expected)),
+            Verification::Unverified => ("unverified", None),
+        };
+        let milliseconds = |time: Option<Duration>| time.map(|time| time.as_secs_f64() * 1000.0);
+
+        Json::object([
+            ("year", self.year.into()),
+            ("day", self.day.into()),
+            ("part", part_number(self.part).into()),
+            ("input", input_name(self.task_type).into()),
+            ("answer", self.answer.into()),
+            ("parse_ms", milliseconds(self.parse_time).into()),
+            ("solve_ms", milliseconds(self.solve_time).into()),
+            ("verification", verification.into()),
+            ("expected", expected.into()),
+            ("status", self.status().to_string().into()),
+            ("error", self.error.as_ref().map(ToString::to_string).into()),
+        ])
+    }
+}
+
+impl Display for RunResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Day{:02} {:?}", self.day, self.part)?;
+
+        match (&self.answer, &self.error, self.verification) {
+            (_, Some(error), _) => write!(f, " failed: {error}"),
+            (Some(answer), None, Verification::Wrong { expected }) => {
+                write!(f, " yields result: {answer} (expected {expected})")
+            }
+            (Some(answer), None, _) => write!(f, " yields result: {answer}"),
+            (None, None, _) => write!(f, " has no result"),
+        }
+    }
+}
+
+/// The results of a run over one or more days.
+#[derive(Debug, Default)]
+pub struct RunReport {
+    pub results: Vec<RunResult>,
+}
+
+impl RunReport {
+    /// Runs both parts of each of `days` in `year`, in order.
+    #[must_use]
+    pub fn run(year: u16, days: impl IntoIterator<Item = u8>, task_type: &TaskType) -> Self {
+        let results = parts(days)
+            .iter()
+            .map(|(day, part)| RunResult::run(year, *day, part, task_type))
+            .collect();
+
+        Self { results }
+    }
+
+    /// Runs both parts of each of `days` in `year` on up to `jobs` threads. The results come back in the
+    /// same order as from [`RunReport::run`], whichever part finishes first.
+    #[must_use]
+    pub fn run_parallel(
+        year: u16,
+        days: impl IntoIterator<Item = u8>,
+        task_type: &TaskType,
+        jobs: usize,
+    ) -> Self {
+        let parts = parts(days);
+        let next = AtomicUsize::new(0);
+        let slots = Mutex::new(
+            parts
+                .iter()
+                .map(|_| None)
+                .collect::<Vec<Option<RunResult>>>(),
+        );
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.clamp(1, parts.len().max(1)) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((day, part)) = parts.get(index) else {
+                        break;
+                    };
+
+                    let result = RunResult::run(year, *day, part, task_type);
+                    slots.lock().unwrap_or_else(PoisonError::into_inner)[index] = Some(result);
+                });
+            }
+        });
+
+        let results = slots
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Self { results }
+    }
+
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(RunResult::passed)
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> Json {
+        let passed = self.results.iter().filter(|result| result.passed()).count();
+
+        Json::object([
+            ("passed", passed.into()),
+            ("failed", (self.results.len() - passed).into()),
+            (
+                "results",
+                Json::Array(self.results.iter().map(RunResult::to_json).collect()),
+            ),
+        ])
+    }
+
+    /// A `JUnit` XML report with a test case for each part of each day. Wrong answers are
+    /// failures and anything that stopped a part producing an answer is an error.
+    #[must_use]
+    pub fn to_junit(&self) -> String {
+        let seconds = |time: Duration| format!("{:.6}", time.as_secs_f64());
+        let time = |result: &RunResult| {
+            result.parse_time.unwrap_or_default() + result.solve_time.unwrap_or_default()
+        };
+
+        let failures = self
+            .results
+            .iter()
+            .filter(|result| matches!(result.verification, Verification::Wrong { .. }))
+            .count();
+        let errors = self
+            .results
+            .iter()
+            .filter(|result| result.error.is_some())
+            .count();
+        let total = self.results.iter().map(time).sum::<Duration>();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuite name=\"aoc-2023-rust\" tests=\"{}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{}\">",
+            self.results.len(),
+            seconds(total)
+        );
+
+        for result in &self.results {
+            let _ = write!(
+                xml,
+                "  <testcase classname=\"{}.day{:02}.{}\" name=\"part_{}\" time=\"{}\">",
+                result.year,
+                result.day,
+                input_name(result.task_type),
+                part_number(result.part),
+                seconds(time(result))
+            );
+
+            match (&result.error, result.answer, result.verification) {
+                (Some(error), _, _) => {
+                    let _ = write!(
+                        xml,
+                        "<error type=\"{}\" message=\"{}\"/>",
+                        result.status(),
+                        escape(&error.to_string())
+                    );
+                }
+                (None, Some(answer), Verification::Wrong { expected }) => {
+                    let _ = write!(
+                        xml,
+                        "<failure message=\"expected {expected}, found {answer}\"/>"
+                    );
+                }
+                (None, Some(answer), _) => {
+                    let _ = write!(xml, "<system-out>{answer}</system-out>");
+                }
+                (None, None, _) => {}
+            }
+
+            xml.push_str("</testcase>\n");
+        }
+
+        xml.push_str("</testsuite>\n");
+        xml
+    }
+}
+
+impl RunReport {
+    /// A table of the status of each part by day, followed by a count of each status.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let status = |day: u8, part: Part| {
+            self.results
+                .iter()
+                .find(|result| result.day == day && result.part == part)
+                .map_or_else(|| "-".to_string(), |result| result.status().to_string())
+        };
+        let mut days = self
+            .results
+            .iter()
+            .map(|result| result.day)
+            .collect::<Vec<u8>>();
+        days.dedup();
+
+        let mut summary = format!("{:>3}  {:<13}  {}\n", "Day", "Part one", "Part two");
+        for day in days {
+            let _ = writeln!(
+                summary,
+                "{day:>3}  {:<13}  {}",
+                status(day, Part::PartOne),
+                status(day, Part::PartTwo)
+            );
+        }
+
+        let counts = [
+            Status::Ok,
+            Status::WrongAnswer,
+            Status::Unsolved,
+            Status::InputMissing,
+            Status::ParseError,
+            Status::Panicked,
+        ]
+        .iter()
+        .filter_map(|status| {
+            let count = self
+                .results
+                .iter()
+                .filter(|result| result.status() == *status)
+                .count();
+            (count > 0).then(|| format!("{count} {status}"))
+        })
+        .collect::<Vec<String>>();
+        let _ = write!(
+            summary,
+            "\nParts: {} ({})",
+            self.results.len(),
+            counts.join(", ")
+        );
+
+        summary
+    }
+}
+
+impl Display for RunReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for result in &self.results {
+            writeln!(f, "{result}")?;
+        }
+
+        Ok(())
+    }
+}
+
+thread_local! {
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static RECORD_PANICS: Once = Once::new();
+
+/// Runs `f`, catching a panic along with where it was raised.
+///
+/// Panics inside `isolate` are recorded rather than printed. Panics anywhere else still go to
+/// the panic hook that was installed before the first call.
+fn isolate<T>(f: impl FnOnce() -> T) -> Result<T, RunError> {
+    RECORD_PANICS.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if ISOLATED.get() {
+                PANIC_LOCATION.set(info.location().map(ToString::to_string));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+
+    ISOLATED.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    ISOLATED.set(false);
+
+    result.map_err(|payload| RunError::Panicked {
+        message: panic_message(payload),
+        location: PANIC_LOCATION.take(),
+    })
+}
+
+/// Both parts of each of `days`, in the order they are reported.
+fn parts(days: impl IntoIterator<Item = u8>) -> Vec<(u8, Part)> {
+    days.into_iter()
+        .flat_map(|day| [Part::PartOne, Part::PartTwo].map(|part| (day, part)))
+        .collect()
+}
+
+const fn part_number(part: Part) -> u8 {
+    match part {
+        Part::PartOne => 1,
+        Part::PartTwo => 2,
+    }
+}
+
+const fn input_name(task_type: TaskType) -> &'static str {
+    match task_type {
+        TaskType::Example => "example",
+        TaskType::Puzzle => "puzzle",
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut escaped, char| {
+            match char {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&apos;"),
+                char => escaped.push(char),
+            }
+            escaped
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_run_is_verified() {
+        let report = RunReport::run(2023, solvers::days(2023), &TaskType::Example);
+
+        assert_eq!(report.results.len(), 14);
+        assert!(report.passed(), "{report}");
+        assert!(report
+            .results
+            .iter()
+            .all(|result| result.verification == Verification::Correct));
+        // Every day parses on its own, so parsing is timed apart from solving.
+        assert!(report
+            .results
+            .iter()
+            .all(|result| result.parse_time.is_some()));
+        assert_eq!(
+            report.results[4].to_string(),
+            "Day03 PartOne yields result: 4361"
+        );
+    }
+
+    #[test]
+    fn parallel_runs_keep_day_order() {
+        let summary = |report: RunReport| {
+            report
+                .results
+                .into_iter()
+                .map(|result| (result.day, result.part, result.answer, result.error))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            summary(RunReport::run_parallel(
+                2023,
+                solvers::days(2023),
+                &TaskType::Example,
+                4
+            )),
+            summary(RunReport::run(
+                2023,
+                solvers::days(2023),
+                &TaskType::Example
+            ))
+        );
+
+        let report = RunReport::run_parallel(2023, [1, 8, 2], &TaskType::Example, 3);
+        assert_eq!(report.results.len(), 6);
+        assert!(report.results[2].error.is_some());
+        assert_eq!(report.results[5].answer, Some(2286));
+    }
+
+    #[test]
+    fn panics_are_isolated() {
+        let line = line!() + 1;
+        let Err(RunError::Panicked { message, location }) = isolate(|| panic!("no answer")) else {
+            panic!("the panic should be caught");
+        };
+
+        assert_eq!(message, "no answer");
+        assert!(
+            location.is_some_and(|location| location.starts_with(&format!("{}:{line}:", file!())))
+        );
+        assert_eq!(isolate(|| 42), Ok(42));
+    }
+
+    #[test]
+    fn summary_counts_each_status() {
+        let mut report = RunReport::run(2023, [2, 8], &TaskType::Example);
+        report.results[1].verification = Verification::Wrong { expected: 1 };
+
+        assert_eq!(
+            report.summary(),
+            "Day  Part one       Part two\n  \
+               2  ok             wrong answer\n  \
+               8  unsolved       unsolved\n\n\
+             Parts: 4 (1 ok, 1 wrong answer, 2 unsolved)"
+        );
+        assert!(!report.passed());
+    }
+
+    #[test]
+    fn results_as_json() {
+        let result = RunResult {
+            year: 2023,
+            day: 4,
+            part: Part::PartTwo,
+            task_type: TaskType::Example,
+            answer: Some(29),
+            parse_time: Some(Duration::from_micros(1500)),
+            solve_time: None,
+            verification: Verification::Wrong { expected: 30 },
+            error: None,
+        };
+
+        assert_eq!(
+            result.to_json().to_string(),
+            "{\"year\":2023,\"day\":4,\"part\":2,\"input\":\"example\",\"answer\":29,\"parse_ms\":1.5,\
+             \"solve_ms\":null,\"verification\":\"wrong\",\"expected\":30,\"status\":\"wrong answer\",\
+             \"error\":null}"
+        );
+        assert!(!result.passed());
+    }
+
+    #[test]
+    fn results_as_junit() {
+        let report = RunReport {
+            results: vec![
+                RunResult::run(2023, 2, &Part::PartOne, &TaskType::Example),
+                RunResult::run(2023, 8, &Part::PartOne, &TaskType::Example),
+            ],
+        };
+        let xml = report.to_junit();
+
+        assert!(xml.contains("tests=\"2\" failures=\"0\" errors=\"1\""));
+        assert!(xml.contains("<testcase classname=\"2023.day02.example\" name=\"part_1\""));
+        assert!(xml.contains("<system-out>8</system-out>"));
+        assert!(xml.contains("<error type=\"unsolved\" message=\"This day is not solved yet\"/>"));
+        assert_eq!(
+            escape("<\"a\" & 'b'>"),
+            "&lt;&quot;a&quot; &amp; &apos;b&apos;&gt;"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Delta debugging for failing inputs: records and then lines are removed for as long as the
+//! failure persists, leaving a small input that still reproduces it.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::solvers::Solver;
+
+/// What counts as the solver still failing on a shrunk input.
+#[derive(Debug, Clone, Copy)]
+pub enum Failure {
+    /// The solver panics.
+    Panics,
+    /// The solver returns an answer other than this one. This suits answers that do not change
+    /// as irrelevant records are removed, such as the lowest location in Day05. Inputs the
+    /// solver panics on do not count, so a wrong answer is not shrunk into a crash.
+    Mismatch(u64),
+    /// The solver disagrees with this reference solver. Inputs the reference itself panics on
+    /// do not count, so the result stays valid input.
+    Reference(Solver),
+}
+
+impl Failure {
+    #[must_use]
+    pub fn reproduces(&self, solver: Solver, data: &str) -> bool {
+        match self {
+            Self::Panics => outcome(solver, data).is_err(),
+            Self::Mismatch(expected) => {
+                matches!(outcome(solver, data), Ok(answer) if answer != *expected)
+            }
+            Self::Reference(reference) => outcome(*reference, data)
+                .is_ok_and(|expected| outcome(solver, data) != Ok(expected)),
+        }
+    }
+}
+
+/// Runs a solver, turning a panic into an error holding its message.
+///
+/// # Errors
+///
+/// Returns the panic message if the solver panics.
+pub fn outcome(solver: Solver, data: &str) -> Result<u64, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| solver(data))).map_err(panic_message)
+}
+
+/// The message a panic was raised with, when it was raised with one.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(ToString::to_string))
+        .unwrap_or_else(|| "panicked".to_string())
+}
+
+/// Shrinks `data` while `fails` holds, or returns `None` if it does not fail to begin with.
+///
+/// Blank-line separated records, such as the maps in Day05, are removed first, then single
+/// lines.
+pub fn shrink(data: &str, fails: impl Fn(&str) -> bool) -> Option<String> {
+    if !fails(data) {
+        return None;
+    }
+
+    let records = minimise(
+        &data.trim_end().split("\n\n").collect::<Vec<&str>>(),
+        "\n\n",
+        &fails,
+    );
+    let lines = minimise(&records.lines().collect::<Vec<&str>>(), "\n", &fails);
+
+    Some(lines)
+}
+
+/// Removes chunks of `units`, halving the chunk size each round, for as long as the joined
+/// input keeps failing.
+fn minimise(units: &[&str], separator: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut units = units.to_vec();
+    let mut chunk = units.len().div_ceil(2);
+
+    while chunk > 0 {
+        let mut start = 0;
+        while start < units.len() {
+            let end = (start + chunk).min(units.len());
+            let candidate = [&units[..start], &units[end..]].concat();
+
+            if !candidate.is_empty() && fails(&candidate.join(separator)) {
+                units = candidate;
+            } else {
+                start = end;
+            }
+        }
+
+        chunk /= 2;
+    }
+
+    units.join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_count(data: &str) -> u64 {
+        data.lines().count() as u64
+    }
+
+    fn panics_on_sevens(data: &str) -> u64 {
+        assert!(!data.contains('7'), "found a seven");
+        line_count(data)
+    }
+
+    #[test]
+    fn shrinks_to_the_panicking_line() {
+        let data = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+
+        assert_eq!(
+            shrink(data, |data| Failure::Panics
+                .reproduces(panics_on_sevens, data)),
+            Some("7".to_string())
+        );
+        assert_eq!(
+            outcome(panics_on_sevens, "7"),
+            Err("found a seven".to_string())
+        );
+    }
+
+    #[test]
+    fn passing_input_does_not_shrink() {
+        assert_eq!(
+            shrink("1\n2", |data| Failure::Panics
+                .reproduces(panics_on_sevens, data)),
+            None
+        );
+    }
+
+    #[test]
+    fn shrinks_whole_records_first() {
+        let data = "1\n2\n\n3\n7\n\n8\n9";
+        let failure = Failure::Panics;
+
+        assert_eq!(
+            minimise(&["1\n2", "3\n7", "8\n9"], "\n\n", |data| failure
+                .reproduces(panics_on_sevens, data)),
+            "3\n7"
+        );
+        assert_eq!(
+            shrink(data, |data| failure.reproduces(panics_on_sevens, data)),
+            Some("7".to_string())
+        );
+    }
+
+    #[test]
+    fn shrinks_against_an_expected_answer() {
+        let failure = Failure::Mismatch(2);
+
+        // A single line fails too, but the shrinker only removes lines while the input keeps
+        // failing. It stops once removing any one line would give the expected two lines, so
+        // the result is 1-minimal rather than the smallest failing input.
+        assert_eq!(
+            shrink("a\nb\nc\nd", |data| failure.reproduces(line_count, data)).as_deref(),
+            Some("b\nc\nd")
+        );
+    }
+
+    #[test]
+    fn wrong_answers_do_not_shrink_into_panics() {
+        fn needs_two_lines(data: &str) -> u64 {
+            assert!(data.lines().count() > 1, "too few lines");
+            line_count(data)
+        }
+        let failure = Failure::Mismatch(1);
+
+        // Any two lines are a wrong answer, and one line is a panic rather than a wrong answer.
+        assert_eq!(
+            shrink("a\nb\nc", |data| failure.reproduces(needs_two_lines, data)).as_deref(),
+            Some("a\nb")
+        );
+        assert!(!failure.reproduces(needs_two_lines, "a"));
+    }
+
+    #[test]
+    fn reference_failures_need_a_valid_input() {
+        let failure = Failure::Reference(line_count);
+
+        assert!(failure.reproduces(|data| line_count(data) + 1, "1"));
+        assert!(!failure.reproduces(line_count, "1"));
+        assert!(!Failure::Reference(panics_on_sevens).reproduces(line_count, "7"));
+    }
+}
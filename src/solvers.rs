@@ -0,0 +1,70 @@
+//! Every day's solvers behind one signature, so that tools can pick a solver by year, day and
+//! part. Each year's module holds its own entries, and this module dispatches to them.
+
+use std::io;
+use std::ops::RangeInclusive;
+
+use crate::{year2023, FileLoader, Part, TaskType};
+
+/// Solves one part of a day's puzzle, panicking on input it cannot handle.
+pub type Solver = fn(&str) -> u64;
+
+/// Parses a day's input without solving it, describing why it is invalid if it is.
+pub type Parser = fn(&str) -> Result<(), String>;
+
+/// The years that have solvers, in order.
+pub const YEARS: [u16; 1] = [year2023::YEAR];
+
+/// The year the command line works on unless told otherwise: the latest one.
+pub const DEFAULT_YEAR: u16 = YEARS[YEARS.len() - 1];
+
+/// The days of `year` that have solvers, in order. A year without solvers has none.
+#[must_use]
+pub const fn days(year: u16) -> RangeInclusive<u8> {
+    match year {
+        year2023::YEAR => year2023::DAYS,
+        _ => RangeInclusive::new(1, 0),
+    }
+}
+
+/// Reads the example or puzzle input for one part of `day` in `year`.
+///
+/// # Errors
+///
+/// Returns an error if the input file is missing or unreadable.
+pub fn input(year: u16, day: u8, part: &Part, task_type: &TaskType) -> io::Result<String> {
+    let infix = match year {
+        year2023::YEAR => year2023::infix(day, part),
+        _ => "",
+    };
+
+    FileLoader::read(year, &format!("{day:02}"), infix, *task_type)
+}
+
+/// The parser for `day` in `year`, or `None` if the day only parses as it solves.
+#[must_use]
+pub fn parser(year: u16, day: u8) -> Option<Parser> {
+    match year {
+        year2023::YEAR => year2023::parser(day),
+        _ => None,
+    }
+}
+
+/// The solver the runner uses for `day` and `part` in `year`, or `None` for a day that is not
+/// solved yet.
+#[must_use]
+pub fn solver(year: u16, day: u8, part: &Part) -> Option<Solver> {
+    match year {
+        year2023::YEAR => year2023::solver(day, part),
+        _ => None,
+    }
+}
+
+/// The naive solver kept alongside an optimised one, for the days and parts that have one.
+#[must_use]
+pub fn reference(year: u16, day: u8, part: &Part) -> Option<Solver> {
+    match year {
+        year2023::YEAR => year2023::reference(day, part),
+        _ => None,
+    }
+}
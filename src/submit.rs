@@ -0,0 +1,582 @@
+//! Submits answers to the configured endpoint and keeps a local store of what each submission
+//! earned, so that an answer already known to be wrong is never sent twice.
+//!
+//! Answers go to the same `AOC_BASE_URL`, with the same session, as inputs are fetched from.
+//! The store is `AOC_ANSWERS_FILE`, or `answers.txt` in this crate's data directory.
+
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::fetch::{data_dir, FetchError, InputSource};
+use crate::Part;
+
+/// What the endpoint made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way.
+    Wrong,
+    /// Submitted too soon after the last answer, and not checked.
+    Wait(Duration),
+    /// The part is already solved, so the answer was not checked.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page the endpoint answers with.
+    #[must_use]
+    pub fn parse(page: &str) -> Option<Self> {
+        let page = page.to_lowercase();
+
+        if page.contains("that's the right answer") {
+            Some(Self::Correct)
+        } else if page.contains("left to wait") {
+            Some(Self::Wait(wait(&page).unwrap_or(Duration::from_mins(1))))
+        } else if page.contains("that's not the right answer") {
+            Some(if page.contains("too high") {
+                Self::TooHigh
+            } else if page.contains("too low") {
+                Self::TooLow
+            } else {
+                Self::Wrong
+            })
+        } else if page.contains("don't seem to be solving the right level") {
+            Some(Self::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too-high",
+            Self::TooLow => "too-low",
+            Self::Wrong => "wrong",
+            Self::Wait(_) => "wait",
+            Self::AlreadySolved => "already-solved",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "That's the right answer"),
+            Self::TooHigh => write!(f, "That answer is too high"),
+            Self::TooLow => write!(f, "That answer is too low"),
+            Self::Wrong => write!(f, "That's not the right answer"),
+            Self::Wait(wait) => write!(f, "Too soon, wait {}s", wait.as_secs()),
+            Self::AlreadySolved => write!(f, "This part is already solved"),
+        }
+    }
+}
+
+/// The time to wait in text like "you have 1m 5s left to wait".
+fn wait(page: &str) -> Option<Duration> {
+    let end = page.find(" left to wait")?;
+    let start = page[..end].rfind("you have ")? + "you have ".len();
+
+    page[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.len().checked_sub(1)?);
+            let number = number.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// One answer that was sent, and what it earned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: u64,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub submitted_at: u64,
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let part = match self.part {
+            Part::PartOne => 1,
+            Part::PartTwo => 2,
+        };
+        write!(
+            f,
+            "{} {} {part} {} {} {}",
+            self.year,
+            self.day,
+            self.answer,
+            self.verdict.name(),
+            self.submitted_at
+        )?;
+
+        match self.verdict {
+            Verdict::Wait(wait) => write!(f, " {}", wait.as_secs()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Submission {
+    fn parse(line: &str) -> Option<Self> {
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+        let [year, day, part, answer, verdict, submitted_at, wait @ ..] = fields.as_slice() else {
+            return None;
+        };
+
+        let verdict = match *verdict {
+            "correct" => Verdict::Correct,
+            "too-high" => Verdict::TooHigh,
+            "too-low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            "wait" => Verdict::Wait(Duration::from_secs(wait.first()?.parse().ok()?)),
+            "already-solved" => Verdict::AlreadySolved,
+            _ => return None,
+        };
+
+        Some(Self {
+            year: year.parse().ok()?,
+            day: day.parse().ok()?,
+            part: match *part {
+                "1" => Part::PartOne,
+                "2" => Part::PartTwo,
+                _ => return None,
+            },
+            answer: answer.parse().ok()?,
+            verdict,
+            submitted_at: submitted_at.parse().ok()?,
+        })
+    }
+}
+
+/// Why an answer was not sent.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved {
+        answer: u64,
+    },
+    KnownWrong(Verdict),
+    /// At least as high as an answer that was too high.
+    TooHigh {
+        than: u64,
+    },
+    /// At most as low as an answer that was too low.
+    TooLow {
+        than: u64,
+    },
+    /// The endpoint asked for this many more seconds before the next answer.
+    RateLimited {
+        seconds: u64,
+    },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadySolved { answer } => {
+                write!(f, "This part is already solved, with {answer}")
+            }
+            Self::KnownWrong(verdict) => {
+                write!(f, "This answer was already submitted: {verdict}")
+            }
+            Self::TooHigh { than } => write!(f, "{than} was already too high"),
+            Self::TooLow { than } => write!(f, "{than} was already too low"),
+            Self::RateLimited { seconds } => {
+                write!(f, "Wait {seconds}s before submitting another answer")
+            }
+        }
+    }
+}
+
+/// Every submission made so far, kept in a text file with one submission per line.
+#[derive(Debug)]
+pub struct AnswerStore {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl AnswerStore {
+    /// The store configured in the environment, or `None` if it has nowhere to go.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store exists but cannot be read.
+    pub fn from_env() -> io::Result<Option<Self>> {
+        let path = std::env::var("AOC_ANSWERS_FILE")
+            .map(PathBuf::from)
+            .ok()
+            .or_else(|| {
+                data_dir(|name| std::env::var(name).ok()).map(|data| data.join("answers.txt"))
+            });
+
+        path.map(Self::open).transpose()
+    }
+
+    /// Opens the store at `path`, which is created on the first submission.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store exists but cannot be read. Lines it cannot make sense of
+    /// are skipped.
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        let submissions = match fs::read_to_string(&path) {
+            Ok(store) => store.lines().filter_map(Submission::parse).collect(),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => vec![],
+            Err(error) => return Err(error),
+        };
+
+        Ok(Self { path, submissions })
+    }
+
+    #[must_use]
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    /// The answer that was accepted for `day` and `part` in `year`, if one was.
+    #[must_use]
+    pub fn correct(&self, year: u16, day: u8, part: &Part) -> Option<u64> {
+        self.submissions
+            .iter()
+            .find(|submission| {
+                submission.year == year
+                    && submission.day == day
+                    && submission.part == *part
+                    && submission.verdict == Verdict::Correct
+            })
+            .map(|submission| submission.answer)
+    }
+
+    /// Checks whether `answer` for `day` and `part` in `year` is worth sending at `now`, in
+    /// seconds since the Unix epoch. A wait applies to every year.
+    ///
+    /// # Errors
+    ///
+    /// Returns why not, if the store already rules the answer out or the endpoint asked for a
+    /// wait that is not over yet.
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: &Part,
+        answer: u64,
+        now: u64,
+    ) -> Result<(), Refusal> {
+        for submission in &self.submissions {
+            if let Verdict::Wait(wait) = submission.verdict {
+                let until = submission.submitted_at + wait.as_secs();
+                if until > now {
+                    return Err(Refusal::RateLimited {
+                        seconds: until - now,
+                    });
+                }
+            }
+        }
+
+        let submissions = self.submissions.iter().filter(|submission| {
+            submission.year == year && submission.day == day && submission.part == *part
+        });
+
+        for submission in submissions {
+            match submission.verdict {
+                Verdict::Correct => {
+                    return Err(Refusal::AlreadySolved {
+                        answer: submission.answer,
+                    })
+                }
+                Verdict::TooHigh if answer >= submission.answer => {
+                    return Err(Refusal::TooHigh {
+                        than: submission.answer,
+                    })
+                }
+                Verdict::TooLow if answer <= submission.answer => {
+                    return Err(Refusal::TooLow {
+                        than: submission.answer,
+                    })
+                }
+                Verdict::Wrong if answer == submission.answer => {
+                    return Err(Refusal::KnownWrong(submission.verdict))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds a submission to the store, on disk as well as in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be written.
+    pub fn record(&mut self, submission: Submission) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut store = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(store, "{submission}")?;
+
+        self.submissions.push(submission);
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Fetch(FetchError),
+    /// The endpoint answered with a page that says nothing recognisable about the answer.
+    UnknownVerdict,
+    Store(io::Error),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Refused(refusal) => write!(f, "Not submitted: {refusal}"),
+            Self::Fetch(error) => write!(f, "{error}"),
+            Self::UnknownVerdict => write!(f, "Could not tell from the response how it went"),
+            Self::Store(error) => write!(f, "Could not record the submission: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+/// Sends `answer` for `day` and `part` in `year` unless the store rules it out, and records the
+/// verdict.
+///
+/// # Errors
+///
+/// Returns an error if the answer is refused, cannot be sent, gets an answer that cannot be
+/// understood or cannot be recorded.
+pub fn submit(
+    source: &InputSource,
+    store: &mut AnswerStore,
+    year: u16,
+    day: u8,
+    part: &Part,
+    answer: u64,
+) -> Result<Verdict, SubmitError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    store
+        .check(year, day, part, answer, now)
+        .map_err(SubmitError::Refused)?;
+
+    let level = match part {
+        Part::PartOne => 1,
+        Part::PartTwo => 2,
+    };
+    let page = source
+        .request(
+            &format!("/{year}/day/{day}/answer"),
+            Some(&format!("level={level}&answer={answer}")),
+        )
+        .map_err(SubmitError::Fetch)?;
+    let verdict = Verdict::parse(&page).ok_or(SubmitError::UnknownVerdict)?;
+
+    store
+        .record(Submission {
+            year,
+            day,
+            part: *part,
+            answer,
+            verdict,
+            submitted_at: now,
+        })
+        .map_err(SubmitError::Store)?;
+
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+
+    use super::*;
+
+    /// Answers each connection with a page from `pages`, sending each request's path and form
+    /// back.
+    fn serve(pages: Vec<&'static str>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (requests, received) = mpsc::channel();
+
+        thread::spawn(move || {
+            for page in pages {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let head = (&mut reader)
+                    .lines()
+                    .map_while(Result::ok)
+                    .take_while(|line| !line.is_empty())
+                    .collect::<Vec<String>>();
+                let length = head
+                    .iter()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .map_or(0, |length| length.parse().unwrap());
+                let mut form = vec![0; length];
+                reader.read_exact(&mut form).unwrap();
+
+                requests
+                    .send(format!("{} {}", head[0], String::from_utf8(form).unwrap()))
+                    .unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{page}",
+                    page.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (format!("http://{address}"), received)
+    }
+
+    fn store(name: &str) -> AnswerStore {
+        let path = env::temp_dir().join(format!("aoc-answers-{name}-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+        AnswerStore::open(path).unwrap()
+    }
+
+    #[test]
+    fn verdicts_are_read_from_the_page() {
+        let verdicts = [
+            "<p>That's the right answer! You are one gold star closer.</p>",
+            "<p>That's not the right answer; your answer is too high.</p>",
+            "<p>That's not the right answer; your answer is too low.</p>",
+            "<p>That's not the right answer. If you're stuck, ...</p>",
+            "<p>You gave an answer too recently. You have 1m 5s left to wait.</p>",
+            "<p>You don't seem to be solving the right level.</p>",
+            "<p>Something else entirely</p>",
+        ]
+        .map(Verdict::parse);
+
+        assert_eq!(
+            verdicts,
+            [
+                Some(Verdict::Correct),
+                Some(Verdict::TooHigh),
+                Some(Verdict::TooLow),
+                Some(Verdict::Wrong),
+                Some(Verdict::Wait(Duration::from_secs(65))),
+                Some(Verdict::AlreadySolved),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn submissions_are_posted_and_recorded() {
+        let (base_url, requests) = serve(vec![
+            "That's not the right answer; your answer is too high.",
+            "That's the right answer!",
+        ]);
+        let source = InputSource::new(&base_url, "secret", env::temp_dir());
+        let mut store = store("posted");
+
+        assert_eq!(
+            submit(&source, &mut store, 2023, 6, &Part::PartTwo, 500).unwrap(),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            requests.recv().unwrap(),
+            "POST /2023/day/6/answer HTTP/1.1 level=2&answer=500"
+        );
+
+        // Known to be too high, so this never reaches the server.
+        assert!(matches!(
+            submit(&source, &mut store, 2023, 6, &Part::PartTwo, 600),
+            Err(SubmitError::Refused(Refusal::TooHigh { than: 500 }))
+        ));
+
+        assert_eq!(
+            submit(&source, &mut store, 2023, 6, &Part::PartTwo, 400).unwrap(),
+            Verdict::Correct
+        );
+
+        let reopened = AnswerStore::open(store.path.clone()).unwrap();
+        assert_eq!(reopened.submissions(), store.submissions());
+        assert_eq!(reopened.correct(2023, 6, &Part::PartTwo), Some(400));
+        assert_eq!(
+            reopened.check(2023, 6, &Part::PartTwo, 400, 0),
+            Err(Refusal::AlreadySolved { answer: 400 })
+        );
+        assert_eq!(reopened.check(2023, 6, &Part::PartOne, 400, 0), Ok(()));
+        fs::remove_file(&store.path).unwrap();
+    }
+
+    #[test]
+    fn waits_are_respected() {
+        let mut store = store("wait");
+        let submission = |answer, verdict, submitted_at| Submission {
+            year: 2023,
+            day: 3,
+            part: Part::PartOne,
+            answer,
+            verdict,
+            submitted_at,
+        };
+        store.record(submission(7, Verdict::Wrong, 100)).unwrap();
+        store
+            .record(submission(8, Verdict::Wait(Duration::from_mins(1)), 110))
+            .unwrap();
+
+        assert_eq!(
+            store.check(2023, 3, &Part::PartOne, 9, 150),
+            Err(Refusal::RateLimited { seconds: 20 })
+        );
+        assert_eq!(
+            store.check(2023, 3, &Part::PartOne, 7, 170),
+            Err(Refusal::KnownWrong(Verdict::Wrong))
+        );
+        assert_eq!(store.check(2023, 3, &Part::PartOne, 8, 170), Ok(()));
+        assert_eq!(
+            fs::read_to_string(&store.path).unwrap(),
+            "2023 3 1 7 wrong 100\n2023 3 1 8 wait 110 60\n"
+        );
+        fs::remove_file(&store.path).unwrap();
+    }
+
+    #[test]
+    fn years_are_kept_apart() {
+        let path = store("years").path;
+        fs::write(&path, "2023 3 1 7 correct 100\n2015 3 1 9 wrong 100\n").unwrap();
+        let store = AnswerStore::open(path).unwrap();
+
+        assert_eq!(store.correct(2023, 3, &Part::PartOne), Some(7));
+        assert_eq!(store.correct(2015, 3, &Part::PartOne), None);
+        assert_eq!(store.check(2015, 3, &Part::PartOne, 8, 170), Ok(()));
+        assert_eq!(
+            store.check(2015, 3, &Part::PartOne, 9, 170),
+            Err(Refusal::KnownWrong(Verdict::Wrong))
+        );
+        fs::remove_file(&store.path).unwrap();
+    }
+}
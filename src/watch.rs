@@ -0,0 +1,287 @@
+//! Watches a day's source and input files by polling their modification times, so that its
+//! answers can be rebuilt and re-run whenever one of them changes.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::env::consts::EXE_SUFFIX;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::SystemTime;
+
+use crate::json::Json;
+use crate::{examples, TaskType};
+
+/// Where the watched build goes, apart from `target` so that the running executable is never
+/// rebuilt underneath itself.
+const TARGET_DIR: &str = "target/watch";
+
+/// The source file, every input file and every named example for `day` in `year`, relative
+/// to the crate root at `root`.
+#[must_use]
+pub fn watched_files(root: &Path, year: u16, day: u8) -> Vec<PathBuf> {
+    let prefix = format!("day{day:02}_");
+    let mut inputs = fs::read_dir(root.join(format!("src/inputs/{year}")))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix))
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("txt"))
+        })
+        .collect::<Vec<PathBuf>>();
+    inputs.sort();
+
+    let mut examples = fs::read_dir(root.join(examples::directory(year, day)))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect::<Vec<PathBuf>>();
+    examples.sort();
+
+    let mut files = vec![root.join(format!("src/year{year}/day{day:02}.rs"))];
+    files.extend(inputs);
+    files.extend(examples);
+    files
+}
+
+/// The modification time of each watched file. A file that goes missing, or that appears,
+/// counts as a change.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Snapshot(Vec<(PathBuf, Option<SystemTime>)>);
+
+impl Snapshot {
+    #[must_use]
+    pub fn take(root: &Path, year: u16, day: u8) -> Self {
+        let times = watched_files(root, year, day)
+            .into_iter()
+            .map(|path| {
+                let modified = fs::metadata(&path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok();
+                (path, modified)
+            })
+            .collect();
+
+        Self(times)
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// The answer line for each part of a day, keyed by part and input.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<String, String>);
+
+impl Answers {
+    /// Collects the answer or error of each part in a report written by `run --json`.
+    pub fn add(&mut self, report: &Json) {
+        let results = report
+            .get("results")
+            .and_then(Json::as_array)
+            .unwrap_or_default();
+
+        for result in results {
+            let field = |name| result.get(name).and_then(Json::as_u64);
+            let (Some(day), Some(part), Some(input)) = (
+                field("day"),
+                field("part"),
+                result.get("input").and_then(Json::as_str),
+            ) else {
+                continue;
+            };
+            let part = if part == 1 { "PartOne" } else { "PartTwo" };
+
+            let outcome = match (
+                result.get("error").and_then(Json::as_str),
+                field("answer"),
+                field("expected"),
+            ) {
+                (Some(error), _, _) => format!("failed: {error}"),
+                (None, Some(answer), Some(expected)) if answer != expected => {
+                    format!("{answer} (expected {expected})")
+                }
+                (None, Some(answer), _) => answer.to_string(),
+                (None, None, _) => "no result".to_string(),
+            };
+
+            self.0
+                .insert(format!("Day{day:02} {part} ({input})"), outcome);
+        }
+    }
+}
+
+/// Lists the current answers, marking each one that changed since the previous run.
+#[must_use]
+pub fn diff(previous: &Answers, current: &Answers) -> String {
+    let mut diff = String::new();
+
+    for (part, answer) in &current.0 {
+        let _ = match previous.0.get(part) {
+            Some(before) if before != answer => {
+                writeln!(diff, "* {part}: {answer} (was {before})")
+            }
+            _ => writeln!(diff, "  {part}: {answer}"),
+        };
+    }
+
+    diff
+}
+
+/// Rebuilds the crate with cargo into its own target directory, then runs `day` in `year` on
+/// its example and puzzle inputs.
+///
+/// # Errors
+///
+/// Returns an error if cargo cannot be started, the build fails, or a run writes no report.
+/// Cargo reports build errors itself.
+pub fn rebuild_and_run(year: u16, day: u8) -> Result<Answers, String> {
+    let built = Command::new("cargo")
+        .args(["build", "--quiet", "--target-dir", TARGET_DIR])
+        .status()
+        .map_err(|error| format!("Could not run cargo: {error}"))?;
+    if !built.success() {
+        return Err("The build failed".to_string());
+    }
+
+    let executable = Path::new(TARGET_DIR)
+        .join("debug")
+        .join(format!("{}{EXE_SUFFIX}", env!("CARGO_PKG_NAME")));
+    let report = env::temp_dir().join(format!("aoc-watch-{}.json", process::id()));
+
+    let mut answers = Answers::default();
+    for task_type in [TaskType::Example, TaskType::Puzzle] {
+        let _ = fs::remove_file(&report);
+        let mut command = Command::new(&executable);
+        command.args([
+            "run",
+            "--year",
+            &year.to_string(),
+            "--day",
+            &day.to_string(),
+        ]);
+        command.arg("--json").arg(&report);
+        if task_type == TaskType::Example {
+            command.arg("--example");
+        }
+
+        // Failing parts are part of the answers, so the exit status is not an error here.
+        command
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .status()
+            .map_err(|error| format!("Could not run {}: {error}", executable.display()))?;
+        let json = fs::read_to_string(&report)
+            .map_err(|error| format!("The run wrote no report: {error}"))?;
+        answers.add(&Json::parse(&json).map_err(|error| error.to_string())?);
+    }
+    let _ = fs::remove_file(&report);
+
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::report::{RunError, RunReport, RunResult, Verification};
+    use crate::Part;
+
+    #[test]
+    fn snapshots_change_with_the_files() {
+        let root = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/inputs/2023")).unwrap();
+        fs::create_dir_all(root.join("src/year2023")).unwrap();
+        fs::write(root.join("src/year2023/day04.rs"), "").unwrap();
+        fs::write(root.join("src/inputs/2023/day04_example.txt"), "").unwrap();
+        fs::write(root.join("src/inputs/2023/day05_example.txt"), "").unwrap();
+        fs::write(root.join("src/inputs/2023/day04_notes.md"), "").unwrap();
+
+        assert_eq!(
+            watched_files(&root, 2023, 4),
+            [
+                root.join("src/year2023/day04.rs"),
+                root.join("src/inputs/2023/day04_example.txt")
+            ]
+        );
+
+        let before = Snapshot::take(&root, 2023, 4);
+        assert_eq!(before, Snapshot::take(&root, 2023, 4));
+
+        File::options()
+            .write(true)
+            .open(root.join("src/inputs/2023/day04_example.txt"))
+            .and_then(|file| file.set_modified(SystemTime::now() + Duration::from_mins(1)))
+            .unwrap();
+        assert_ne!(before, Snapshot::take(&root, 2023, 4));
+
+        fs::write(root.join("src/inputs/2023/day04_puzzle.TXT"), "").unwrap();
+        fs::create_dir_all(root.join("src/inputs/2023/day04/examples")).unwrap();
+        fs::write(root.join("src/inputs/2023/day04/examples/one.txt"), "").unwrap();
+        assert_eq!(Snapshot::take(&root, 2023, 4).len(), 4);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    fn report(results: Vec<RunResult>) -> Json {
+        RunReport { results }.to_json()
+    }
+
+    fn result(part: Part, task_type: TaskType, answer: Option<u64>) -> RunResult {
+        RunResult {
+            year: 2023,
+            day: 4,
+            part,
+            task_type,
+            answer,
+            parse_time: None,
+            solve_time: None,
+            verification: Verification::Unverified,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn changed_answers_are_marked() {
+        let mut previous = Answers::default();
+        previous.add(&report(vec![
+            result(Part::PartOne, TaskType::Example, Some(13)),
+            result(Part::PartTwo, TaskType::Example, Some(30)),
+        ]));
+
+        let mut wrong = result(Part::PartTwo, TaskType::Example, Some(31));
+        wrong.verification = Verification::Wrong { expected: 30 };
+        let mut missing = result(Part::PartOne, TaskType::Puzzle, None);
+        missing.error = Some(RunError::InputMissing("not found".to_string()));
+        let mut current = Answers::default();
+        current.add(&report(vec![
+            result(Part::PartOne, TaskType::Example, Some(13)),
+            wrong,
+        ]));
+        current.add(&report(vec![missing]));
+
+        assert_eq!(
+            diff(&previous, &current),
+            "  Day04 PartOne (example): 13\n\
+             \x20 Day04 PartOne (puzzle): failed: Could not read the input: not found\n\
+             * Day04 PartTwo (example): 31 (expected 30) (was 30)\n"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! The 2023 event: each day's solution, and the entries `solvers` dispatches to for this year.
+
+use std::fmt::Display;
+
+use crate::solvers::{Parser, Solver};
+use crate::Part;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+
+use day01::{DigitlessLines, Language, Vocabulary, WordReplacement};
+use day02::{Bag, Duplicates};
+use day04::Overflow;
+
+pub const YEAR: u16 = 2023;
+
+/// The days that have solvers, in order.
+pub const DAYS: std::ops::RangeInclusive<u8> = 1..=7;
+
+/// The infix of the input for `day` and `part`. Day 1 is the only day with a different input
+/// for each part.
+#[must_use]
+pub const fn infix(day: u8, part: &Part) -> &'static str {
+    match (day, part) {
+        (1, Part::PartOne) => "part1",
+        (1, Part::PartTwo) => "part2",
+        _ => "",
+    }
+}
+
+#[must_use]
+pub fn parser(day: u8) -> Option<Parser> {
+    let parser: Parser = match day {
+        // Spelled-out digits are read for both parts, as part two's lines may have no others.
+        1 => |data| {
+            day01::calibrate(
+                data.as_bytes(),
+                &WordReplacement::Vocabulary(Vocabulary::builtin(Language::English)),
+                &DigitlessLines::Reject,
+            )
+            .map(drop)
+            .map_err(|error| error.to_string())
+        },
+        2 => |data| {
+            day02::parse(data, &Duplicates::Reject)
+                .map(drop)
+                .map_err(|error| error.to_string())
+        },
+        3 => |data| day03::parse(data).map_err(|error| error.to_string()),
+        4 => |data| day04::parse(data).map_err(|error| error.to_string()),
+        5 => |data| day05::parse(data).map_err(|error| error.to_string()),
+        6 => |data| day06::parse(data).map_err(|error| error.to_string()),
+        7 => |data| day07::parse(data).map_err(|error| error.to_string()),
+        _ => return None,
+    };
+
+    Some(parser)
+}
+
+#[must_use]
+pub fn solver(day: u8, part: &Part) -> Option<Solver> {
+    let solver: Solver = match (day, part) {
+        (1, Part::PartOne) => |data| day01::run(data, &WordReplacement::Disabled),
+        (1, Part::PartTwo) => |data| {
+            day01::run(
+                data,
+                &WordReplacement::Vocabulary(Vocabulary::builtin(Language::English)),
+            )
+        },
+        (2, Part::PartOne) => |data| answer(day02::part_one(data, &Bag::standard())),
+        (2, Part::PartTwo) => |data| answer(day02::part_two(data)),
+        (3, Part::PartOne) => |data| answer(day03::part_one(data)),
+        (3, Part::PartTwo) => |data| answer(day03::part_two(data)),
+        (4, Part::PartOne) => |data| answer(day04::part_one(data)),
+        (4, Part::PartTwo) => |data| answer(day04::part_two(data, &Overflow::Error)),
+        (5, Part::PartOne) => |data| answer(day05::part_one(data)),
+        (5, Part::PartTwo) => |data| answer(day05::part_two(data)),
+        (6, Part::PartOne) => |data| answer(day06::part_one(data)),
+        (6, Part::PartTwo) => |data| answer(day06::part_two(data)),
+        (7, Part::PartOne) => |data| answer(day07::part_one(data)),
+        (7, Part::PartTwo) => |data| answer(day07::part_two(data)),
+        _ => return None,
+    };
+
+    Some(solver)
+}
+
+/// Unwraps an answer the way the runner does, panicking with the error message.
+fn answer<T: Into<u64>, E: Display>(result: Result<T, E>) -> u64 {
+    result.map_or_else(|error| panic!("{error}"), Into::into)
+}
+
+#[must_use]
+pub fn reference(day: u8, part: &Part) -> Option<Solver> {
+    let solver: Solver = match (day, part) {
+        (2, Part::PartOne) => |data| day02::reference::part_one(data, &Bag::standard()),
+        (2, Part::PartTwo) => day02::reference::part_two,
+        (4, Part::PartOne) => |data| u64::from(day04::reference::part_one(data)),
+        (4, Part::PartTwo) => |data| u64::from(day04::reference::part_two(data)),
+        (5, Part::PartTwo) => day05::reference::part_two,
+        (6, Part::PartOne) => day06::reference::part_one,
+        (6, Part::PartTwo) => day06::reference::part_two,
+        _ => return None,
+    };
+
+    Some(solver)
+}
@@ -0,0 +1,882 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, BufRead};
+use std::iter::zip;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::YEAR;
+use crate::{DayRunner, FileLoader, Part, TaskType};
+
+#[derive(Debug)]
+pub struct Day01;
+
+impl DayRunner for Day01 {
+    fn run(part: Part) {
+        let result = match part {
+            Part::PartOne => run(
+                &FileLoader::load_with_infix(YEAR, "01", "part1", TaskType::Puzzle),
+                &WordReplacement::Disabled,
+            ),
+            Part::PartTwo => run(
+                &FileLoader::load_with_infix(YEAR, "01", "part2", TaskType::Puzzle),
+                &WordReplacement::Vocabulary(Vocabulary::builtin(Language::English)),
+            ),
+        };
+
+        Self::report_result(Self, part, result);
+    }
+}
+
+/// Which spelled-out digits, if any, count alongside the digits themselves.
+pub enum WordReplacement {
+    Disabled,
+    Vocabulary(Vocabulary),
+}
+
+/// The languages with a built-in [`Vocabulary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+impl Language {
+    /// The spelling of each digit, indexed by the digit.
+    const fn digit_words(self) -> [&'static str; 10] {
+        match self {
+            Self::English => [
+                "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+            ],
+            Self::French => [
+                "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+            ],
+            Self::German => [
+                "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+            ],
+            Self::Spanish => [
+                "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+            ],
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "en" | "english" => Ok(Self::English),
+            "fr" | "french" => Ok(Self::French),
+            "de" | "german" => Ok(Self::German),
+            "es" | "spanish" => Ok(Self::Spanish),
+            _ => Err(format!("Unknown language: {value}")),
+        }
+    }
+}
+
+/// A table of words standing in for digits, and how loosely to match them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u8)>,
+    language: Option<Language>,
+    case_insensitive: bool,
+    reversed: bool,
+}
+
+impl Vocabulary {
+    /// The words for one to nine in `language`.
+    #[must_use]
+    pub fn builtin(language: Language) -> Self {
+        let words = zip(1.., &language.digit_words()[1..])
+            .map(|(digit, word)| ((*word).to_string(), digit))
+            .collect();
+
+        Self {
+            words,
+            language: Some(language),
+            case_insensitive: false,
+            reversed: false,
+        }
+    }
+
+    /// Reads a table with one `word=digit` entry per line. Blank lines and lines starting
+    /// with `#` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry has no `=`, an empty word, or a value that is not a
+    /// single digit.
+    pub fn from_table(table: &str) -> Result<Self, VocabularyError> {
+        let words = table
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(line_number, line)| {
+                let invalid = || VocabularyError::InvalidEntry {
+                    line_number,
+                    entry: line.to_string(),
+                };
+
+                let (word, digit) = line.split_once('=').ok_or_else(invalid)?;
+                let (word, digit) = (word.trim(), digit.trim().parse::<u8>());
+
+                match digit {
+                    Ok(digit @ 0..=9) if !word.is_empty() => Ok((word.to_string(), digit)),
+                    _ => Err(invalid()),
+                }
+            })
+            .collect::<Result<Vec<(String, u8)>, VocabularyError>>()?;
+
+        Ok(Self {
+            words,
+            language: None,
+            case_insensitive: false,
+            reversed: false,
+        })
+    }
+
+    /// Reads a table from a file, in the format described by [`Vocabulary::from_table`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or holds an invalid entry.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, VocabularyError> {
+        Self::from_table(&fs::read_to_string(path).map_err(VocabularyError::Io)?)
+    }
+
+    /// Also match the word for zero. Tables read from a file list zero themselves, so this
+    /// only affects built-in languages.
+    #[must_use]
+    pub fn with_zero(mut self) -> Self {
+        if let Some(language) = self.language {
+            self.words.push((language.digit_words()[0].to_string(), 0));
+        }
+        self
+    }
+
+    #[must_use]
+    pub const fn case_insensitive(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+
+    /// Also match each word spelled backwards, so `eno` counts as a one.
+    #[must_use]
+    pub const fn with_reversed(mut self) -> Self {
+        self.reversed = true;
+        self
+    }
+
+    fn patterns(&self) -> impl Iterator<Item = (String, u8)> + '_ {
+        self.words.iter().flat_map(|(word, digit)| {
+            let word = if self.case_insensitive {
+                word.chars().map(fold_case).collect()
+            } else {
+                word.clone()
+            };
+            let reversed = self
+                .reversed
+                .then(|| (word.chars().rev().collect::<String>(), *digit));
+
+            std::iter::once((word, *digit)).chain(reversed)
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum VocabularyError {
+    Io(io::Error),
+    InvalidEntry { line_number: usize, entry: String },
+}
+
+impl Display for VocabularyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read vocabulary: {error}"),
+            Self::InvalidEntry { line_number, entry } => write!(
+                f,
+                "Line {line_number} of the vocabulary should be `word=digit`, found `{entry}`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VocabularyError {}
+
+/// Lower-cases a character, leaving it alone if its lower-case form is more than one character.
+fn fold_case(char: char) -> char {
+    let mut lower = char.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(folded), None) => folded,
+        _ => char,
+    }
+}
+
+pub(crate) fn run(data: &str, word_replacement: &WordReplacement) -> u64 {
+    calibrate(data.as_bytes(), word_replacement, &DigitlessLines::Reject)
+        .unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// What to do with a line that holds no digits at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigitlessLines {
+    /// Stop with [`CalibrationError::NoDigits`].
+    Reject,
+    /// Leave the line out of the calibration, with a warning.
+    Skip,
+    /// Count the line as a value of zero, with a warning.
+    ScoreZero,
+}
+
+#[derive(Debug)]
+pub enum CalibrationError {
+    Io(io::Error),
+    NoDigits { line_number: usize },
+    Overflow { line_number: usize },
+}
+
+impl Display for CalibrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read calibration document: {error}"),
+            Self::NoDigits { line_number } => write!(f, "Line {line_number} has no digits"),
+            Self::Overflow { line_number } => {
+                write!(f, "Calibration sum overflows a u64 at line {line_number}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+/// A digit, or a word standing for one, found on a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub text: String,
+    /// The index of the token's first character within the line.
+    pub position: usize,
+    pub digit: u8,
+}
+
+/// How a single line of the document was calibrated. Both tokens are missing for a digitless
+/// line that was scored as zero.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LineCalibration {
+    pub line_number: usize,
+    pub first: Option<Token>,
+    pub last: Option<Token>,
+    pub value: u8,
+}
+
+/// Every calibrated line of a document, along with the digitless lines that were let through.
+#[derive(Debug)]
+pub struct CalibrationReport {
+    pub lines: Vec<LineCalibration>,
+    pub digitless_lines: Vec<usize>,
+    pub digitless: DigitlessLines,
+    pub total: u64,
+}
+
+impl Display for CalibrationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn describe(token: Option<&Token>) -> String {
+            token.map_or_else(
+                || "-".to_string(),
+                |token| format!("{} at {}", token.text, token.position),
+            )
+        }
+
+        writeln!(
+            f,
+            "{:>6}  {:<16}  {:<16}  {:>5}",
+            "Line", "First", "Last", "Value"
+        )?;
+        for line in &self.lines {
+            writeln!(
+                f,
+                "{:>6}  {:<16}  {:<16}  {:>5}",
+                line.line_number,
+                describe(line.first.as_ref()),
+                describe(line.last.as_ref()),
+                line.value
+            )?;
+        }
+
+        let treatment = match self.digitless {
+            DigitlessLines::ScoreZero => "scored as zero",
+            DigitlessLines::Reject | DigitlessLines::Skip => "skipped",
+        };
+        for line_number in &self.digitless_lines {
+            writeln!(f, "warning: line {line_number} has no digits, {treatment}")?;
+        }
+
+        writeln!(f, "Total: {}", self.total)
+    }
+}
+
+/// Sums the calibration values of a document, reading it a line at a time so that the whole
+/// document never has to be held in memory.
+///
+/// # Errors
+///
+/// Returns an error if `reader` fails, if the sum does not fit in a `u64`, or if a line has no
+/// digits and `digitless` is [`DigitlessLines::Reject`].
+pub fn calibrate(
+    reader: impl BufRead,
+    word_replacement: &WordReplacement,
+    digitless: &DigitlessLines,
+) -> Result<u64, CalibrationError> {
+    scan_lines(reader, word_replacement, *digitless, |_, _| {})
+}
+
+/// Calibrates a document as [`calibrate`] does, keeping the tokens picked from every line.
+///
+/// # Errors
+///
+/// See [`calibrate`].
+pub fn explain(
+    reader: impl BufRead,
+    word_replacement: &WordReplacement,
+    digitless: &DigitlessLines,
+) -> Result<CalibrationReport, CalibrationError> {
+    let mut lines = vec![];
+    let mut digitless_lines = vec![];
+    let total = scan_lines(
+        reader,
+        word_replacement,
+        *digitless,
+        |line_number, calibration| match calibration {
+            Some(line) => {
+                if line.first.is_none() {
+                    digitless_lines.push(line_number);
+                }
+                lines.push(line);
+            }
+            None => digitless_lines.push(line_number),
+        },
+    )?;
+
+    Ok(CalibrationReport {
+        lines,
+        digitless_lines,
+        digitless: *digitless,
+        total,
+    })
+}
+
+/// Calibrates each line of `reader`, visiting every line number with its calibration, or with
+/// `None` for a digitless line that is skipped.
+fn scan_lines(
+    mut reader: impl BufRead,
+    word_replacement: &WordReplacement,
+    digitless: DigitlessLines,
+    mut visit: impl FnMut(usize, Option<LineCalibration>),
+) -> Result<u64, CalibrationError> {
+    let scanner = DigitScanner::new(word_replacement);
+    let mut line = String::new();
+    let mut total = 0u64;
+    let mut line_number = 0;
+
+    while reader.read_line(&mut line).map_err(CalibrationError::Io)? > 0 {
+        line_number += 1;
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+
+        let calibration = match (scanner.first(trimmed), scanner.last(trimmed), digitless) {
+            (Some(first), Some(last), _) => Some(LineCalibration {
+                line_number,
+                value: first.digit * 10 + last.digit,
+                first: Some(first),
+                last: Some(last),
+            }),
+            (_, _, DigitlessLines::Reject) => {
+                return Err(CalibrationError::NoDigits { line_number })
+            }
+            (_, _, DigitlessLines::Skip) => None,
+            (_, _, DigitlessLines::ScoreZero) => Some(LineCalibration {
+                line_number,
+                first: None,
+                last: None,
+                value: 0,
+            }),
+        };
+
+        if let Some(calibration) = &calibration {
+            total = total
+                .checked_add(u64::from(calibration.value))
+                .ok_or(CalibrationError::Overflow { line_number })?;
+        }
+        visit(line_number, calibration);
+        line.clear();
+    }
+
+    Ok(total)
+}
+
+/// Finds the first and last digit on a line, where a digit may also be spelled out.
+///
+/// Spelled-out digits can share letters, as in `eightwo`, which holds both an `eight` and a
+/// `two`. Rather than rewriting the line, the scanner looks for the first digit by reading the
+/// line forwards, and for the last digit by reading it backwards against the reversed words,
+/// so an overlap is never consumed by the match next to it. Backwards, the match that starts
+/// last in the line is the one that ends first, even when it sits inside a longer word.
+struct DigitScanner {
+    forward: Automaton,
+    backward: Automaton,
+    case_insensitive: bool,
+}
+
+impl DigitScanner {
+    fn new(word_replacement: &WordReplacement) -> Self {
+        let digits = (0..=9u8).map(|digit| (char::from(b'0' + digit).to_string(), digit));
+        let (patterns, case_insensitive) = match word_replacement {
+            WordReplacement::Disabled => (digits.collect::<Vec<(String, u8)>>(), false),
+            WordReplacement::Vocabulary(vocabulary) => (
+                digits.chain(vocabulary.patterns()).collect(),
+                vocabulary.case_insensitive,
+            ),
+        };
+
+        Self {
+            forward: Automaton::new(
+                patterns
+                    .iter()
+                    .map(|(pattern, digit)| (pattern.chars().collect(), *digit)),
+            ),
+            backward: Automaton::new(
+                patterns
+                    .iter()
+                    .map(|(pattern, digit)| (pattern.chars().rev().collect(), *digit)),
+            ),
+            case_insensitive,
+        }
+    }
+
+    fn first(&self, line: &str) -> Option<Token> {
+        let (start, length, digit) = self
+            .forward
+            .leftmost(line.chars().map(|char| self.fold(char)))?;
+
+        Some(Self::token(line, start, length, digit))
+    }
+
+    fn last(&self, line: &str) -> Option<Token> {
+        let (reversed_start, length, digit) = self
+            .backward
+            .earliest_ending(line.chars().rev().map(|char| self.fold(char)))?;
+        let start = line.chars().count() - reversed_start - length;
+
+        Some(Self::token(line, start, length, digit))
+    }
+
+    fn token(line: &str, start: usize, length: usize, digit: u8) -> Token {
+        Token {
+            text: line.chars().skip(start).take(length).collect(),
+            position: start,
+            digit,
+        }
+    }
+
+    fn fold(&self, char: char) -> char {
+        if self.case_insensitive {
+            fold_case(char)
+        } else {
+            char
+        }
+    }
+}
+
+/// An Aho-Corasick automaton over the characters of a set of patterns, each labelled with the
+/// digit it stands for.
+struct Automaton {
+    transitions: Vec<HashMap<char, usize>>,
+    failures: Vec<usize>,
+    /// The `(length, digit)` of every pattern that ends at each state, including those reached
+    /// through failure links.
+    outputs: Vec<Vec<(usize, u8)>>,
+    longest_pattern: usize,
+}
+
+impl Automaton {
+    const ROOT: usize = 0;
+
+    fn new(patterns: impl Iterator<Item = (Vec<char>, u8)>) -> Self {
+        let mut automaton = Self {
+            transitions: vec![HashMap::new()],
+            failures: vec![Self::ROOT],
+            outputs: vec![vec![]],
+            longest_pattern: 0,
+        };
+
+        for (pattern, digit) in patterns {
+            let mut state = Self::ROOT;
+            for char in &pattern {
+                state = if let Some(next) = automaton.transitions[state].get(char) {
+                    *next
+                } else {
+                    automaton.transitions.push(HashMap::new());
+                    automaton.failures.push(Self::ROOT);
+                    automaton.outputs.push(vec![]);
+
+                    let next = automaton.transitions.len() - 1;
+                    automaton.transitions[state].insert(*char, next);
+                    next
+                };
+            }
+
+            automaton.outputs[state].push((pattern.len(), digit));
+            automaton.longest_pattern = automaton.longest_pattern.max(pattern.len());
+        }
+
+        // Breadth first, so every failure target is complete before the states relying on it.
+        let mut queue = automaton.transitions[Self::ROOT]
+            .values()
+            .copied()
+            .collect::<VecDeque<usize>>();
+
+        while let Some(state) = queue.pop_front() {
+            let edges = automaton.transitions[state]
+                .iter()
+                .map(|(char, next)| (*char, *next))
+                .collect::<Vec<(char, usize)>>();
+
+            for (char, next) in edges {
+                let failure = automaton.step(automaton.failures[state], char);
+                automaton.failures[next] = failure;
+
+                let inherited = automaton.outputs[failure].clone();
+                automaton.outputs[next].extend(inherited);
+                queue.push_back(next);
+            }
+        }
+
+        automaton
+    }
+
+    fn step(&self, mut state: usize, char: char) -> usize {
+        loop {
+            if let Some(next) = self.transitions[state].get(&char) {
+                return *next;
+            }
+            if state == Self::ROOT {
+                return Self::ROOT;
+            }
+            state = self.failures[state];
+        }
+    }
+
+    /// The `(start, length, digit)` of the match starting earliest in `chars`, preferring the
+    /// longest match when several start at the same place.
+    fn leftmost(&self, chars: impl Iterator<Item = char>) -> Option<(usize, usize, u8)> {
+        let mut state = Self::ROOT;
+        let mut best: Option<(usize, usize, u8)> = None;
+
+        for (index, char) in chars.enumerate() {
+            // Once a match is known, nothing ending this far on can start before it.
+            if let Some((start, _, _)) = best {
+                if index >= start + self.longest_pattern {
+                    break;
+                }
+            }
+
+            state = self.step(state, char);
+            for (length, digit) in &self.outputs[state] {
+                let start = index + 1 - length;
+                let is_better = best.is_none_or(|(best_start, best_length, _)| {
+                    (start, Reverse(*length)) < (best_start, Reverse(best_length))
+                });
+
+                if is_better {
+                    best = Some((start, *length, *digit));
+                }
+            }
+        }
+
+        best
+    }
+
+    /// The `(start, length, digit)` of the match ending earliest in `chars`, preferring the
+    /// longest match when several end at the same place.
+    fn earliest_ending(&self, chars: impl Iterator<Item = char>) -> Option<(usize, usize, u8)> {
+        let mut state = Self::ROOT;
+
+        for (index, char) in chars.enumerate() {
+            state = self.step(state, char);
+            if let Some((length, digit)) =
+                self.outputs[state].iter().max_by_key(|(length, _)| *length)
+            {
+                return Some((index + 1 - length, *length, *digit));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english() -> WordReplacement {
+        WordReplacement::Vocabulary(Vocabulary::builtin(Language::English))
+    }
+
+    #[test]
+    fn part_one_example_input() {
+        assert_eq!(
+            run(
+                &FileLoader::load_with_infix(YEAR, "01", "part1", TaskType::Example),
+                &WordReplacement::Disabled
+            ),
+            142
+        );
+    }
+
+    #[test]
+    fn part_one_puzzle_input() {
+        assert_eq!(
+            run(
+                &FileLoader::load_with_infix(YEAR, "01", "part1", TaskType::Puzzle),
+                &WordReplacement::Disabled
+            ),
+            54632
+        );
+    }
+
+    #[test]
+    fn part_two_example_input() {
+        assert_eq!(
+            run(
+                &FileLoader::load_with_infix(YEAR, "01", "part2", TaskType::Example),
+                &english()
+            ),
+            281
+        );
+    }
+
+    #[test]
+    fn part_two_puzzle_input() {
+        assert_eq!(
+            run(
+                &FileLoader::load_with_infix(YEAR, "01", "part2", TaskType::Puzzle),
+                &english()
+            ),
+            54019
+        );
+    }
+
+    #[test]
+    fn large_input_sums_past_u16_max() {
+        let lines = 100_000;
+        let data = (0..lines)
+            .map(|index| match index % 2 {
+                0 => "x9yz8",
+                _ => "nine7eightwo",
+            })
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        assert_eq!(
+            run(&data, &WordReplacement::Disabled),
+            (98 + 77) * lines / 2
+        );
+        assert_eq!(run(&data, &english()), (98 + 92) * lines / 2);
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let scanner = DigitScanner::new(&english());
+
+        for (line, first, last) in [
+            ("eightwo", 8, 2),
+            ("nineight", 9, 8),
+            ("threeight", 3, 8),
+            ("oneight2twone", 1, 1),
+            ("xtwone3four", 2, 4),
+        ] {
+            assert_eq!(
+                scanner.first(line).map(|token| token.digit),
+                Some(first),
+                "first digit of {line}"
+            );
+            assert_eq!(
+                scanner.last(line).map(|token| token.digit),
+                Some(last),
+                "last digit of {line}"
+            );
+        }
+    }
+
+    #[test]
+    fn calibrate_streams_lines() {
+        let reader = io::BufReader::with_capacity(
+            4,
+            "two1nine\r\nabcone2threexyz\n7pqrstsixteen".as_bytes(),
+        );
+
+        assert_eq!(
+            calibrate(reader, &english(), &DigitlessLines::Reject).unwrap(),
+            29 + 13 + 76
+        );
+    }
+
+    #[test]
+    fn builtin_languages() {
+        for (language, line, first, last) in [
+            (Language::French, "x3quatreneufy", 3, 9),
+            (Language::German, "fünfzweiundvierzig", 5, 4),
+            (Language::Spanish, "seis7cero", 6, 7),
+        ] {
+            let scanner =
+                DigitScanner::new(&WordReplacement::Vocabulary(Vocabulary::builtin(language)));
+
+            assert_eq!(
+                scanner.first(line).map(|token| token.digit),
+                Some(first),
+                "first digit of {line}"
+            );
+            assert_eq!(
+                scanner.last(line).map(|token| token.digit),
+                Some(last),
+                "last digit of {line}"
+            );
+        }
+
+        let vocabulary = Vocabulary::builtin(Language::Spanish).with_zero();
+        let scanner = DigitScanner::new(&WordReplacement::Vocabulary(vocabulary));
+        assert_eq!(scanner.last("seis7cero").map(|token| token.digit), Some(0));
+    }
+
+    #[test]
+    fn case_insensitive_and_reversed_words() {
+        let vocabulary = Vocabulary::builtin(Language::German)
+            .case_insensitive()
+            .with_reversed();
+        let scanner = DigitScanner::new(&WordReplacement::Vocabulary(vocabulary));
+
+        assert_eq!(scanner.first("xFÜNFx").map(|token| token.digit), Some(5));
+        assert_eq!(scanner.last("Acht1thca").map(|token| token.digit), Some(8));
+        assert_eq!(
+            scanner.last("x2SIEBEN4snie").map(|token| token.digit),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn vocabulary_from_table() {
+        let vocabulary = Vocabulary::from_table("# Dutch\neen=1\ntwee = 2\n\nnul=0\n").unwrap();
+        let scanner = DigitScanner::new(&WordReplacement::Vocabulary(vocabulary));
+
+        assert_eq!(scanner.first("nultwee").map(|token| token.digit), Some(0));
+        assert_eq!(scanner.last("nultweeen").map(|token| token.digit), Some(1));
+
+        // `ne` starts inside `one`, and after it, so it is the last digit.
+        let vocabulary = Vocabulary::from_table("one=1\nne=9").unwrap();
+        let scanner = DigitScanner::new(&WordReplacement::Vocabulary(vocabulary));
+        let last = scanner.last("x7one").unwrap();
+        assert_eq!(
+            (last.text.as_str(), last.position, last.digit),
+            ("ne", 3, 9)
+        );
+        assert_eq!(scanner.first("onex").map(|token| token.digit), Some(1));
+
+        assert!(matches!(
+            Vocabulary::from_table("een=1\ntwee=12"),
+            Err(VocabularyError::InvalidEntry { line_number: 2, .. })
+        ));
+        assert!(matches!(
+            Vocabulary::from_table("=1"),
+            Err(VocabularyError::InvalidEntry { line_number: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn explain_reports_tokens_per_line() {
+        let report = explain(
+            b"two1nine\nxtwone3fourx".as_slice(),
+            &english(),
+            &DigitlessLines::Reject,
+        )
+        .unwrap();
+
+        assert_eq!(
+            report.lines[1],
+            LineCalibration {
+                line_number: 2,
+                first: Some(Token {
+                    text: "two".to_string(),
+                    position: 1,
+                    digit: 2
+                }),
+                last: Some(Token {
+                    text: "four".to_string(),
+                    position: 7,
+                    digit: 4
+                }),
+                value: 24,
+            }
+        );
+        assert_eq!(report.total, 29 + 24);
+    }
+
+    #[test]
+    fn digitless_lines() {
+        let data = "1abc2\nnothing\n\nx7x";
+
+        assert!(matches!(
+            calibrate(
+                data.as_bytes(),
+                &WordReplacement::Disabled,
+                &DigitlessLines::Reject
+            ),
+            Err(CalibrationError::NoDigits { line_number: 2 })
+        ));
+
+        let skipped = explain(
+            data.as_bytes(),
+            &WordReplacement::Disabled,
+            &DigitlessLines::Skip,
+        )
+        .unwrap();
+        assert_eq!(skipped.total, 12 + 77);
+        assert_eq!(skipped.lines.len(), 2);
+        assert_eq!(skipped.digitless_lines, vec![2, 3]);
+
+        let zeroed = explain(
+            data.as_bytes(),
+            &WordReplacement::Disabled,
+            &DigitlessLines::ScoreZero,
+        )
+        .unwrap();
+        assert_eq!(zeroed.total, 12 + 77);
+        assert_eq!(zeroed.lines.len(), 4);
+        assert_eq!(zeroed.lines[2].value, 0);
+        assert_eq!(zeroed.digitless_lines, vec![2, 3]);
+    }
+
+    #[test]
+    fn trailing_and_all_digitless_lines() {
+        for (data, lines, digitless_lines) in [
+            ("1abc2\nnothing", 1, vec![2]),
+            ("1abc2\nnothing\n\n", 1, vec![2, 3]),
+            ("none\nat\nall", 0, vec![1, 2, 3]),
+        ] {
+            for digitless in [DigitlessLines::Skip, DigitlessLines::ScoreZero] {
+                let report =
+                    explain(data.as_bytes(), &WordReplacement::Disabled, &digitless).unwrap();
+                let expected_lines = match digitless {
+                    DigitlessLines::Skip => lines,
+                    _ => lines + digitless_lines.len(),
+                };
+
+                assert_eq!(report.lines.len(), expected_lines, "{data:?}");
+                assert_eq!(report.digitless_lines, digitless_lines, "{data:?}");
+            }
+        }
+    }
+}
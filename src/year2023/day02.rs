@@ -0,0 +1,1073 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use super::YEAR;
+use crate::json::Json;
+use crate::{DayRunner, FileLoader, Part, TaskType};
+
+#[derive(Debug)]
+pub struct Day02;
+
+impl DayRunner for Day02 {
+    fn run(part: Part) {
+        let result = match part {
+            Part::PartOne => part_one(
+                &FileLoader::load(YEAR, "02", TaskType::Puzzle),
+                &Bag::standard(),
+            ),
+            Part::PartTwo => part_two(&FileLoader::load(YEAR, "02", TaskType::Puzzle)),
+        }
+        .unwrap_or_else(|error| panic!("{error}"));
+
+        Self::report_result(Self, part, result);
+    }
+}
+
+pub(crate) fn part_one(data: &str, bag: &Bag) -> Result<u64, GameError> {
+    let games = parse(data, &Duplicates::Reject)?;
+
+    Ok(games.possible_games(bag).into_iter().map(u64::from).sum())
+}
+
+pub(crate) fn part_two(data: &str) -> Result<u64, GameError> {
+    let games = parse(data, &Duplicates::Reject)?;
+
+    games
+        .games()
+        .iter()
+        .enumerate()
+        .try_fold(0u64, |total, (index, game)| {
+            games
+                .checked_power(game)
+                .and_then(|power| total.checked_add(power))
+                .ok_or(GameError {
+                    line_number: index + 1,
+                    column: 1,
+                    kind: GameErrorKind::Overflow,
+                })
+        })
+}
+
+/// Parses one game per line, reducing each to the most cubes of each color it pulled.
+///
+/// Colors are borrowed from `data` rather than copied, and are numbered in the order they
+/// are first seen so that each game can keep its counts in a color-indexed array.
+///
+/// # Errors
+///
+/// Returns the position of the first line that is not a valid game, or that mentions a color
+/// twice in one pull when `duplicates` is [`Duplicates::Reject`].
+pub fn parse<'a>(data: &'a str, duplicates: &Duplicates) -> Result<Games<'a>, GameError> {
+    let mut colors = vec![];
+    let mut games = vec![];
+
+    for (index, line) in data.lines().enumerate() {
+        games.push(MaxPullsGame::new(line, index + 1, duplicates, &mut colors)?);
+    }
+
+    Ok(Games { colors, games })
+}
+
+/// A set of games, along with every color any of them pulled.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Games<'a> {
+    colors: Vec<&'a str>,
+    games: Vec<MaxPullsGame>,
+}
+
+impl<'a> Games<'a> {
+    /// Every color seen in any game, in the order they were first seen.
+    #[must_use]
+    pub fn colors(&self) -> &[&'a str] {
+        &self.colors
+    }
+
+    #[must_use]
+    pub fn games(&self) -> &[MaxPullsGame] {
+        &self.games
+    }
+
+    /// The numbers of the games that could have been played with `bag`.
+    #[must_use]
+    pub fn possible_games(&self, bag: &Bag) -> Vec<u32> {
+        let limits = self
+            .colors
+            .iter()
+            .map(|color| bag.count(color))
+            .collect::<Vec<u32>>();
+
+        self.games
+            .iter()
+            .filter(|game| {
+                game.max_pulls
+                    .iter()
+                    .zip(&limits)
+                    .all(|(count, limit)| count <= limit)
+            })
+            .map(|game| game.game_number)
+            .collect()
+    }
+
+    /// The smallest bag `game` could have been played with.
+    ///
+    /// The bag covers every color seen in any of the games, so a game that never pulls one of
+    /// them needs none of it.
+    #[must_use]
+    pub fn minimal_bag(&self, game: &MaxPullsGame) -> Bag {
+        self.colors
+            .iter()
+            .enumerate()
+            .map(|(color, name)| (*name, game.max_pulls.count(color)))
+            .collect()
+    }
+
+    /// The power of the minimal bag for `game`, without building the bag, saturating at
+    /// `u64::MAX`.
+    #[must_use]
+    pub fn power(&self, game: &MaxPullsGame) -> u64 {
+        self.checked_power(game).unwrap_or(u64::MAX)
+    }
+
+    /// The power of the minimal bag for `game`, or `None` if it overflows a u64.
+    #[must_use]
+    pub fn checked_power(&self, game: &MaxPullsGame) -> Option<u64> {
+        if self.colors.is_empty() {
+            return Some(0);
+        }
+
+        (0..self.colors.len())
+            .map(|color| u64::from(game.max_pulls.count(color)))
+            .try_fold(1u64, u64::checked_mul)
+    }
+
+    /// Checks every game against `bag`, noting which colors rule each game out.
+    #[must_use]
+    pub fn analyse(&self, bag: &Bag) -> Analysis {
+        let games = self
+            .games
+            .iter()
+            .map(|game| {
+                let max_pulls = self.minimal_bag(game);
+                let excess = max_pulls
+                    .cubes
+                    .iter()
+                    .filter(|(color, count)| **count > bag.count(color))
+                    .map(|(color, count)| (color.clone(), count - bag.count(color)))
+                    .collect();
+
+                GameAnalysis {
+                    game_number: game.game_number,
+                    power: self.power(game),
+                    max_pulls,
+                    excess,
+                }
+            })
+            .collect();
+
+        let mut colors = self
+            .colors
+            .iter()
+            .map(|color| (*color).to_string())
+            .collect::<Vec<String>>();
+        colors.sort();
+
+        Analysis {
+            bag: bag.clone(),
+            colors,
+            games,
+        }
+    }
+}
+
+/// How one game fares against a bag.
+#[derive(Debug, PartialEq, Eq)]
+pub struct GameAnalysis {
+    pub game_number: u32,
+    /// The smallest bag the game could have been played with.
+    pub max_pulls: Bag,
+    /// Each color the game pulled more of than the bag holds, with how many more.
+    pub excess: Vec<(String, u32)>,
+    pub power: u64,
+}
+
+impl GameAnalysis {
+    #[must_use]
+    pub const fn feasible(&self) -> bool {
+        self.excess.is_empty()
+    }
+}
+
+/// Every game checked against one bag, with statistics across the whole file.
+#[derive(Debug)]
+pub struct Analysis {
+    pub bag: Bag,
+    /// Every color pulled in any game, in alphabetical order.
+    pub colors: Vec<String>,
+    pub games: Vec<GameAnalysis>,
+}
+
+/// The statistics of one color across every game.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ColorSummary {
+    /// The most cubes of the color pulled at once in any game.
+    pub max_pulled: u32,
+    /// How many games pulled more of the color than the bag holds.
+    pub infeasible_games: usize,
+    pub largest_excess: u32,
+}
+
+impl Analysis {
+    pub fn feasible_games(&self) -> impl Iterator<Item = &GameAnalysis> {
+        self.games.iter().filter(|game| game.feasible())
+    }
+
+    /// The sum of the numbers of the feasible games, which is the answer to part one.
+    #[must_use]
+    pub fn feasible_game_sum(&self) -> u64 {
+        self.feasible_games()
+            .map(|game| u64::from(game.game_number))
+            .sum()
+    }
+
+    /// The sum of the power of every game, which is the answer to part two, saturating at
+    /// `u64::MAX`.
+    #[must_use]
+    pub fn power_sum(&self) -> u64 {
+        self.games
+            .iter()
+            .map(|game| game.power)
+            .fold(0, u64::saturating_add)
+    }
+
+    #[must_use]
+    pub fn color_summary(&self, color: &str) -> ColorSummary {
+        let excesses = self.games.iter().filter_map(|game| {
+            game.excess
+                .iter()
+                .find(|(excess_color, _)| excess_color == color)
+                .map(|(_, excess)| *excess)
+        });
+
+        ColorSummary {
+            max_pulled: self
+                .games
+                .iter()
+                .map(|game| game.max_pulls.count(color))
+                .max()
+                .unwrap_or(0),
+            infeasible_games: excesses.clone().count(),
+            largest_excess: excesses.max().unwrap_or(0),
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> Json {
+        let counts = |bag: &Bag| {
+            Json::object(
+                bag.cubes
+                    .iter()
+                    .map(|(color, count)| (color.as_str(), (*count).into())),
+            )
+        };
+
+        let games = self
+            .games
+            .iter()
+            .map(|game| {
+                Json::object([
+                    ("game", game.game_number.into()),
+                    ("max", counts(&game.max_pulls)),
+                    ("feasible", game.feasible().into()),
+                    (
+                        "excess",
+                        Json::object(
+                            game.excess
+                                .iter()
+                                .map(|(color, excess)| (color.as_str(), (*excess).into())),
+                        ),
+                    ),
+                    ("power", game.power.into()),
+                ])
+            })
+            .collect::<Vec<Json>>();
+
+        let colors = self.colors.iter().map(|color| {
+            let summary = self.color_summary(color);
+            (
+                color.as_str(),
+                Json::object([
+                    ("max_pulled", summary.max_pulled.into()),
+                    ("infeasible_games", summary.infeasible_games.into()),
+                    ("largest_excess", summary.largest_excess.into()),
+                ]),
+            )
+        });
+
+        Json::object([
+            ("bag", counts(&self.bag)),
+            ("games", Json::Array(games)),
+            (
+                "summary",
+                Json::object([
+                    ("games", self.games.len().into()),
+                    ("feasible", self.feasible_games().count().into()),
+                    (
+                        "infeasible",
+                        (self.games.len() - self.feasible_games().count()).into(),
+                    ),
+                    ("feasible_game_sum", self.feasible_game_sum().into()),
+                    ("power_sum", self.power_sum().into()),
+                    ("colors", Json::object(colors)),
+                ]),
+            ),
+        ])
+    }
+}
+
+impl Display for Analysis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let widths = self
+            .colors
+            .iter()
+            .map(|color| color.len().max(5))
+            .collect::<Vec<usize>>();
+
+        writeln!(f, "Bag: {}", self.bag)?;
+        writeln!(f)?;
+
+        write!(f, "{:>6}", "Game")?;
+        for (color, width) in self.colors.iter().zip(&widths) {
+            write!(f, "  {color:>width$}")?;
+        }
+        writeln!(f, "  {:<8}  {:<24}  {:>10}", "Feasible", "Over by", "Power")?;
+
+        for game in &self.games {
+            write!(f, "{:>6}", game.game_number)?;
+            for (color, width) in self.colors.iter().zip(&widths) {
+                write!(f, "  {:>width$}", game.max_pulls.count(color))?;
+            }
+
+            let over_by = match game.excess.as_slice() {
+                [] => "-".to_string(),
+                excess => excess
+                    .iter()
+                    .map(|(color, excess)| format!("{color} +{excess}"))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            };
+            let feasible = if game.feasible() { "yes" } else { "no" };
+
+            writeln!(f, "  {feasible:<8}  {over_by:<24}  {:>10}", game.power)?;
+        }
+
+        let feasible = self.feasible_games().count();
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Games: {} ({feasible} feasible, {} infeasible)",
+            self.games.len(),
+            self.games.len() - feasible
+        )?;
+        writeln!(
+            f,
+            "Sum of feasible game numbers: {}",
+            self.feasible_game_sum()
+        )?;
+        writeln!(f, "Sum of powers: {}", self.power_sum())?;
+        for color in &self.colors {
+            let summary = self.color_summary(color);
+            writeln!(
+                f,
+                "{color}: max {} pulled, {} game(s) over the bag, by up to {}",
+                summary.max_pulled, summary.infeasible_games, summary.largest_excess
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// What to do when one pull mentions the same color more than once, as in `3 red, 4 red`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Add the counts together.
+    Sum,
+    /// Treat the game as invalid.
+    Reject,
+}
+
+/// Where and why a line could not be read as a game. Columns count from 1.
+#[derive(Debug, PartialEq, Eq)]
+pub struct GameError {
+    pub line_number: usize,
+    pub column: usize,
+    pub kind: GameErrorKind,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GameErrorKind {
+    MissingColon,
+    InvalidGameNumber,
+    InvalidPull(String),
+    DuplicateColor(String),
+    /// The counts, or the answer they feed into, do not fit the integer holding them.
+    Overflow,
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}, column {}: ", self.line_number, self.column)?;
+
+        match &self.kind {
+            GameErrorKind::MissingColon => write!(f, "expected `Game <number>:`"),
+            GameErrorKind::InvalidGameNumber => write!(f, "expected a game number"),
+            GameErrorKind::InvalidPull(pull) => {
+                write!(f, "expected `<count> <color>`, found `{pull}`")
+            }
+            GameErrorKind::DuplicateColor(color) => {
+                write!(f, "{color} appears more than once in the same pull")
+            }
+            GameErrorKind::Overflow => write!(f, "the cube counts overflow"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// A number of cubes of each color.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Bag {
+    cubes: BTreeMap<String, u32>,
+}
+
+impl Bag {
+    /// The bag from the puzzle: 12 red, 13 green and 14 blue cubes.
+    #[must_use]
+    pub fn standard() -> Self {
+        Self::from_iter([("red", 12), ("green", 13), ("blue", 14)])
+    }
+
+    /// Reads `color=count` entries separated by commas or newlines, such as
+    /// `red=12, green=13, blue=14`. Lines starting with `#` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for an entry that is not a color and a count.
+    pub fn parse(spec: &str) -> Result<Self, BagError> {
+        spec.lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(|line| line.split(','))
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                entry
+                    .split_once('=')
+                    .map(|(color, count)| (color.trim(), count.trim().parse::<u32>()))
+                    .and_then(|(color, count)| match (color, count) {
+                        ("", _) | (_, Err(_)) => None,
+                        (color, Ok(count)) => Some((color.to_string(), count)),
+                    })
+                    .ok_or_else(|| BagError::InvalidEntry(entry.to_string()))
+            })
+            .collect::<Result<BTreeMap<String, u32>, BagError>>()
+            .map(|cubes| Self { cubes })
+    }
+
+    /// Reads a bag from a file, in the format described by [`Bag::parse`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or holds an invalid entry.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, BagError> {
+        Self::parse(&fs::read_to_string(path).map_err(|error| BagError::Io(error.to_string()))?)
+    }
+
+    #[must_use]
+    pub fn count(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = &str> {
+        self.cubes.keys().map(String::as_str)
+    }
+
+    /// Whether this bag has at least as many cubes of every color as `other`.
+    #[must_use]
+    pub fn contains(&self, other: &Self) -> bool {
+        other
+            .cubes
+            .iter()
+            .all(|(color, count)| self.count(color) >= *count)
+    }
+
+    /// The product of the counts of every color in the bag, or zero for a bag with no
+    /// colors at all, saturating at `u64::MAX`.
+    #[must_use]
+    pub fn power(&self) -> u64 {
+        if self.cubes.is_empty() {
+            return 0;
+        }
+
+        self.cubes
+            .values()
+            .try_fold(1u64, |power, count| power.checked_mul(u64::from(*count)))
+            .unwrap_or(u64::MAX)
+    }
+}
+
+impl<'a> FromIterator<(&'a str, u32)> for Bag {
+    fn from_iter<T: IntoIterator<Item = (&'a str, u32)>>(iter: T) -> Self {
+        Self {
+            cubes: iter
+                .into_iter()
+                .map(|(color, count)| (color.to_string(), count))
+                .collect(),
+        }
+    }
+}
+
+impl Display for Bag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let entries = self
+            .cubes
+            .iter()
+            .map(|(color, count)| format!("{color}={count}"))
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", entries.join(", "))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BagError {
+    Io(String),
+    InvalidEntry(String),
+}
+
+impl Display for BagError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read bag: {error}"),
+            Self::InvalidEntry(entry) => {
+                write!(f, "Bag entries should be `color=count`, found `{entry}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BagError {}
+
+/// A game reduced to the most cubes of each color seen in any one pull, indexed by the
+/// color numbering of its [`Games`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaxPullsGame {
+    pub game_number: u32,
+    max_pulls: CubeCounts,
+}
+
+impl MaxPullsGame {
+    fn new<'a>(
+        game_line: &'a str,
+        line_number: usize,
+        duplicates: &Duplicates,
+        colors: &mut Vec<&'a str>,
+    ) -> Result<Self, GameError> {
+        // Every piece is a slice of `game_line`, so its column is its offset within the line.
+        let error_at = |piece: &str, kind: GameErrorKind| GameError {
+            line_number,
+            column: piece.as_ptr() as usize - game_line.as_ptr() as usize + 1,
+            kind,
+        };
+
+        let (game_info_str, cube_pulls_str) = game_line
+            .split_once(':')
+            .ok_or_else(|| error_at(game_line, GameErrorKind::MissingColon))?;
+
+        let game_number = game_info_str
+            .split_once(' ')
+            .and_then(|(_game_string, game_number)| game_number.trim().parse::<u32>().ok())
+            .ok_or_else(|| error_at(game_info_str, GameErrorKind::InvalidGameNumber))?;
+
+        let mut max_pulls = CubeCounts::new();
+
+        // A game with nothing after the colon never pulled any cubes.
+        if cube_pulls_str.trim().is_empty() {
+            return Ok(Self {
+                game_number,
+                max_pulls,
+            });
+        }
+
+        let mut color_count: SmallVec<(usize, u32), INLINE_COLORS> = SmallVec::new();
+
+        // A single pass over the bytes, reading `<count> <color>` up to each `,` or `;`.
+        let bytes = cube_pulls_str.as_bytes();
+        let skip_whitespace = |mut index: usize| {
+            while bytes.get(index).is_some_and(u8::is_ascii_whitespace) {
+                index += 1;
+            }
+            index
+        };
+        let mut index = 0;
+
+        loop {
+            let pull_start = index;
+            index = skip_whitespace(index);
+
+            let mut count = Some(0u32);
+            let count_start = index;
+            while let Some(digit @ b'0'..=b'9') = bytes.get(index) {
+                count = count
+                    .and_then(|count| count.checked_mul(10))
+                    .and_then(|count| count.checked_add(u32::from(digit - b'0')));
+                index += 1;
+            }
+            let count_end = index;
+
+            index = skip_whitespace(index);
+            let color_start = index;
+            while !matches!(bytes.get(index), None | Some(b',' | b';')) {
+                index += 1;
+            }
+            let end = index;
+            let color = cube_pulls_str[color_start..end].trim_ascii_end();
+
+            let (Some(count), false, false, false) = (
+                count,
+                count_start == count_end,
+                count_end == color_start,
+                color.is_empty(),
+            ) else {
+                let pull = cube_pulls_str[pull_start..end].trim_ascii();
+                return Err(error_at(pull, GameErrorKind::InvalidPull(pull.to_string())));
+            };
+
+            let color_index = colors
+                .iter()
+                .position(|known| *known == color)
+                .unwrap_or_else(|| {
+                    colors.push(color);
+                    colors.len() - 1
+                });
+
+            let seen = color_count
+                .iter_mut()
+                .find(|(index, _)| *index == color_index);
+
+            match (seen, duplicates) {
+                (None, _) => color_count.push((color_index, count)),
+                (Some((_, total)), Duplicates::Sum) => {
+                    *total = total.checked_add(count).ok_or_else(|| {
+                        error_at(&cube_pulls_str[count_start..end], GameErrorKind::Overflow)
+                    })?;
+                }
+                (Some(_), Duplicates::Reject) => {
+                    return Err(error_at(
+                        &cube_pulls_str[count_start..end],
+                        GameErrorKind::DuplicateColor(color.to_string()),
+                    ))
+                }
+            }
+
+            let separator = bytes.get(end).copied();
+            if separator != Some(b',') {
+                // The end of a pull, so its counts can be folded into the maximums.
+                for (color_index, count) in color_count.iter() {
+                    max_pulls.raise(*color_index, *count);
+                }
+                color_count.clear();
+            }
+            if separator.is_none() {
+                break;
+            }
+
+            index = end + 1;
+        }
+
+        Ok(Self {
+            game_number,
+            max_pulls,
+        })
+    }
+
+    /// The most cubes of the color numbered `color` seen in any one pull.
+    #[must_use]
+    pub fn max_pulls(&self, color: usize) -> u32 {
+        self.max_pulls.count(color)
+    }
+}
+
+/// Enough inline room for the puzzle's three colors and one more before anything allocates.
+const INLINE_COLORS: usize = 4;
+
+/// Cube counts indexed by color number, where a color past the end has no cubes.
+type CubeCounts = SmallVec<u32, INLINE_COLORS>;
+
+impl CubeCounts {
+    fn count(&self, color: usize) -> u32 {
+        self.get(color).unwrap_or(0)
+    }
+
+    fn raise(&mut self, color: usize, count: u32) {
+        while self.len() <= color {
+            self.push(0);
+        }
+
+        if let Some(max) = self.iter_mut().nth(color) {
+            *max = (*max).max(count);
+        }
+    }
+}
+
+/// A vector that keeps its first `N` items inline, only allocating once it grows past them.
+#[derive(Debug, Clone)]
+struct SmallVec<T, const N: usize> {
+    inline: [T; N],
+    len: usize,
+    spilled: Vec<T>,
+}
+
+impl<T: Copy + Default, const N: usize> SmallVec<T, N> {
+    fn new() -> Self {
+        Self {
+            inline: [T::default(); N],
+            len: 0,
+            spilled: Vec::new(),
+        }
+    }
+
+    const fn len(&self) -> usize {
+        self.len
+    }
+
+    fn push(&mut self, item: T) {
+        if self.len < N {
+            self.inline[self.len] = item;
+        } else {
+            self.spilled.push(item);
+        }
+        self.len += 1;
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+        self.spilled.clear();
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        match index {
+            index if index >= self.len => None,
+            index if index < N => Some(self.inline[index]),
+            index => Some(self.spilled[index - N]),
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        self.inline[..self.len.min(N)].iter().chain(&self.spilled)
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.inline[..self.len.min(N)]
+            .iter_mut()
+            .chain(&mut self.spilled)
+    }
+}
+
+impl<T: Copy + Default + PartialEq, const N: usize> PartialEq for SmallVec<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Copy + Default + Eq, const N: usize> Eq for SmallVec<T, N> {}
+
+/// A naive parser, which keeps every pull as a map from color name to count. It is kept as a
+/// baseline to check and time the compact parser against, and to shrink inputs with.
+///
+/// Unlike the original parser, which kept the first count of a color repeated within a pull,
+/// it adds the counts up, as [`Duplicates::Sum`](super::Duplicates::Sum) does.
+pub(crate) mod reference {
+    use std::collections::HashMap;
+
+    use super::Bag;
+
+    fn parse(data: &str) -> Vec<(u32, HashMap<String, u32>)> {
+        data.lines()
+            .map(|line| {
+                let (game_info_str, cube_pulls_str) = line.split_once(':').unwrap();
+                let (_game_string, game_number) = game_info_str.split_once(' ').unwrap();
+
+                let pulls: Vec<HashMap<String, u32>> = cube_pulls_str
+                    .split(';')
+                    .map(|pulls| {
+                        let mut color_count: HashMap<String, u32> = HashMap::new();
+                        pulls.split(',').map(str::trim).for_each(|pull| {
+                            let (count, color) = pull.split_once(' ').unwrap();
+                            *color_count.entry(color.to_string()).or_insert(0) +=
+                                count.parse::<u32>().unwrap();
+                        });
+                        color_count
+                    })
+                    .collect();
+
+                let mut max_pulls: HashMap<String, u32> = HashMap::new();
+                for (color, count) in pulls.iter().flatten() {
+                    let max = max_pulls.entry(color.clone()).or_insert(0);
+                    *max = (*max).max(*count);
+                }
+
+                (game_number.parse::<u32>().unwrap(), max_pulls)
+            })
+            .collect()
+    }
+
+    pub fn part_one(data: &str, bag: &Bag) -> u64 {
+        parse(data)
+            .iter()
+            .filter(|(_, max_pulls)| {
+                max_pulls
+                    .iter()
+                    .all(|(color, count)| bag.count(color) >= *count)
+            })
+            .map(|(game_number, _)| u64::from(*game_number))
+            .sum()
+    }
+
+    pub fn part_two(data: &str) -> u64 {
+        let games = parse(data);
+        let mut colors = games
+            .iter()
+            .flat_map(|(_, max_pulls)| max_pulls.keys())
+            .collect::<Vec<&String>>();
+        colors.sort();
+        colors.dedup();
+
+        games
+            .iter()
+            .map(|(_, max_pulls)| {
+                colors
+                    .iter()
+                    .map(|color| u64::from(max_pulls.get(*color).copied().unwrap_or(0)))
+                    .product::<u64>()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn part_one_example_input() {
+        assert_eq!(
+            part_one(
+                &FileLoader::load(YEAR, "02", TaskType::Example),
+                &Bag::standard()
+            ),
+            Ok(8)
+        );
+    }
+
+    #[test]
+    fn part_one_puzzle_input() {
+        assert_eq!(
+            part_one(
+                &FileLoader::load(YEAR, "02", TaskType::Puzzle),
+                &Bag::standard()
+            ),
+            Ok(2268)
+        );
+    }
+
+    #[test]
+    fn part_two_example_input() {
+        assert_eq!(
+            part_two(&FileLoader::load(YEAR, "02", TaskType::Example)),
+            Ok(2286)
+        );
+    }
+
+    #[test]
+    fn part_two_puzzle_input() {
+        assert_eq!(
+            part_two(&FileLoader::load(YEAR, "02", TaskType::Puzzle)),
+            Ok(63542)
+        );
+    }
+
+    #[test]
+    fn open_color_set() {
+        let data = "Game 1: 3 teal, 2 red; 1 mauve\nGame 2: 4 teal\nGame 3: 2 red, 1 mauve, 2 teal";
+        let bag = Bag::parse("teal=3,\nred=2, mauve=1").unwrap();
+
+        let games = parse(data, &Duplicates::Reject).unwrap();
+
+        assert_eq!(games.possible_games(&bag), vec![1, 3]);
+        assert_eq!(
+            games
+                .games()
+                .iter()
+                .map(|game| games.minimal_bag(game).to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "mauve=1, red=2, teal=3",
+                "mauve=0, red=0, teal=4",
+                "mauve=1, red=2, teal=2"
+            ]
+        );
+        assert_eq!(part_two(data), Ok(6 + 4));
+    }
+
+    #[test]
+    fn bag_rejects_invalid_entries() {
+        assert_eq!(
+            Bag::parse("red=12, green"),
+            Err(BagError::InvalidEntry("green".to_string()))
+        );
+        assert_eq!(
+            Bag::parse("=4"),
+            Err(BagError::InvalidEntry("=4".to_string()))
+        );
+    }
+
+    #[test]
+    fn duplicate_colors_are_summed() {
+        let games = parse(
+            "Game 1: 3 red, 4 red; 2 blue, 1 red, 5 blue",
+            &Duplicates::Sum,
+        )
+        .unwrap();
+
+        assert_eq!(
+            games.minimal_bag(&games.games()[0]),
+            Bag::from_iter([("red", 7), ("blue", 7)])
+        );
+    }
+
+    #[test]
+    fn duplicate_colors_are_rejected() {
+        let error = parse(
+            "Game 1: 3 red\nGame 2: 1 blue; 3 red, 4 red",
+            &Duplicates::Reject,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            GameError {
+                line_number: 2,
+                column: 24,
+                kind: GameErrorKind::DuplicateColor("red".to_string()),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Line 2, column 24: red appears more than once in the same pull"
+        );
+    }
+
+    #[test]
+    fn game_with_zero_pulls() {
+        let games = parse(
+            "Game 1: 3 red\nGame 2:\nGame 3: 2 blue",
+            &Duplicates::Reject,
+        )
+        .unwrap();
+
+        assert_eq!(games.possible_games(&Bag::default()), vec![2]);
+        assert_eq!(
+            games.minimal_bag(&games.games()[1]),
+            Bag::from_iter([("red", 0), ("blue", 0)])
+        );
+        assert_eq!(games.power(&games.games()[1]), 0);
+        assert_eq!(Bag::default().power(), 0);
+    }
+
+    #[test]
+    fn malformed_pull() {
+        assert_eq!(
+            parse("Game 7: 3 red, red", &Duplicates::Sum),
+            Err(GameError {
+                line_number: 1,
+                column: 16,
+                kind: GameErrorKind::InvalidPull("red".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn many_colors_spill_past_inline_counts() {
+        let data = "Game 1: 1 a, 2 b, 3 c, 4 d, 5 e, 6 f; 7 a, 1 f\nGame 2: 2 f";
+        let games = parse(data, &Duplicates::Reject).unwrap();
+
+        assert_eq!(games.colors(), ["a", "b", "c", "d", "e", "f"]);
+        assert_eq!(games.games()[0].max_pulls(0), 7);
+        assert_eq!(games.games()[0].max_pulls(5), 6);
+        assert_eq!(games.games()[1].max_pulls(5), 2);
+        assert_eq!(games.power(&games.games()[0]), 7 * 2 * 3 * 4 * 5 * 6);
+        assert_eq!(part_two(data), Ok(reference::part_two(data)));
+    }
+
+    #[test]
+    fn analyse_example_input() {
+        let data = FileLoader::load(YEAR, "02", TaskType::Example);
+        let games = parse(&data, &Duplicates::Reject).unwrap();
+        let analysis = games.analyse(&Bag::standard());
+
+        assert_eq!(
+            analysis.games[3],
+            GameAnalysis {
+                game_number: 4,
+                max_pulls: Bag::from_iter([("red", 14), ("green", 3), ("blue", 15)]),
+                excess: vec![("blue".to_string(), 1), ("red".to_string(), 2)],
+                power: 630,
+            }
+        );
+        assert_eq!(analysis.feasible_game_sum(), 8);
+        assert_eq!(analysis.power_sum(), 2286);
+        assert_eq!(
+            analysis.color_summary("red"),
+            ColorSummary {
+                max_pulled: 20,
+                infeasible_games: 2,
+                largest_excess: 8,
+            }
+        );
+
+        let json = analysis.to_json().to_string();
+        assert!(json.starts_with(r#"{"bag":{"blue":14,"green":13,"red":12},"games":[{"game":1,"#));
+        assert!(json.contains(r#""excess":{"blue":1,"red":2},"power":630"#));
+    }
+
+    /// A million games in the puzzle's format, drawn from a fixed-seed generator.
+    #[test]
+    #[ignore = "benchmark; run with `cargo test --release -- --ignored --nocapture`"]
+    fn benchmark_million_games() {
+        let data = crate::generate::games(0x2023, 1_000_000, 6, 20);
+        let bag = Bag::standard();
+
+        let start = Instant::now();
+        let expected = (reference::part_one(&data, &bag), reference::part_two(&data));
+        let reference_time = start.elapsed();
+
+        let start = Instant::now();
+        let games = parse(&data, &Duplicates::Reject).unwrap();
+        let parse_time = start.elapsed();
+        let actual = (
+            games.possible_games(&bag).into_iter().map(u64::from).sum(),
+            games.games().iter().map(|game| games.power(game)).sum(),
+        );
+        let compact_time = start.elapsed();
+
+        // Timings depend on the machine, so they are reported rather than checked.
+        println!(
+            "reference: {reference_time:?}, compact: {compact_time:?} (parsing {parse_time:?})"
+        );
+        assert_eq!(actual, expected);
+    }
+}
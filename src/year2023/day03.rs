@@ -0,0 +1,245 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use regex::{Captures, Regex};
+
+use super::YEAR;
+use crate::{DayRunner, FileLoader, Part, TaskType};
+
+#[derive(Debug)]
+pub struct Day03;
+
+impl DayRunner for Day03 {
+    fn run(part: Part) {
+        let result = match part {
+            Part::PartOne => part_one(&FileLoader::load(YEAR, "03", TaskType::Puzzle)),
+            Part::PartTwo => part_two(&FileLoader::load(YEAR, "03", TaskType::Puzzle)),
+        }
+        .unwrap_or_else(|error| panic!("{error}"));
+
+        Self::report_result(Self, part, result);
+    }
+}
+
+struct Number {
+    row_index: usize,
+    column_start_index: usize,
+    column_end_index: usize,
+    value: u32,
+    visited: bool,
+}
+
+impl Number {
+    const fn visit(&mut self) {
+        self.visited = true;
+    }
+}
+
+#[derive(Debug)]
+struct Symbol {
+    row_index: usize,
+    column_index: usize,
+}
+
+impl Symbol {
+    fn visit<'a>(&'a self, numbers: &'a mut [Number]) -> Vec<&'a mut Number> {
+        let (row_index, column_index) = (self.row_index, self.column_index);
+        let mut visited_numbers: Vec<&mut Number> = vec![];
+
+        // Compare distances rather than listing the neighbouring cells, so that a symbol on the
+        // top row or left column has no neighbours at index -1.
+        for number in numbers.iter_mut() {
+            if number.row_index.abs_diff(row_index) <= 1
+                && number.column_start_index <= column_index + 1
+                && column_index <= number.column_end_index + 1
+            {
+                number.visit();
+                visited_numbers.push(number);
+            }
+        }
+
+        visited_numbers
+    }
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub(crate) fn parse(data: &str) -> Result<(), SchematicError> {
+    extract_numbers(data, &Regex::new(r"(\d+)+").unwrap())?;
+    extract_symbols(data, &Regex::new(r"[^\d.\n]").unwrap());
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SchematicError {
+    NumberTooLarge { line_number: usize, column: usize },
+    Overflow,
+}
+
+impl Display for SchematicError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NumberTooLarge {
+                line_number,
+                column,
+            } => write!(
+                f,
+                "Line {line_number}, column {column}: the number does not fit in a u32"
+            ),
+            Self::Overflow => write!(f, "The answer overflows a u32"),
+        }
+    }
+}
+
+impl std::error::Error for SchematicError {}
+
+pub(crate) fn part_one(data: &str) -> Result<u32, SchematicError> {
+    let mut numbers = extract_numbers(data, &Regex::new(r"(\d+)+").unwrap())?;
+    let symbols = extract_symbols(data, &Regex::new(r"[^\d.\n]").unwrap());
+
+    for symbol in &symbols {
+        symbol.visit(&mut numbers);
+    }
+
+    numbers
+        .iter()
+        .filter(|number| number.visited)
+        .try_fold(0u32, |total, number| total.checked_add(number.value))
+        .ok_or(SchematicError::Overflow)
+}
+
+#[allow(clippy::trivial_regex)]
+pub(crate) fn part_two(data: &str) -> Result<u32, SchematicError> {
+    let mut numbers = extract_numbers(data, &Regex::new(r"(\d+)+").unwrap())?;
+    let symbols = extract_symbols(data, &Regex::new(r"[*]").unwrap());
+
+    let mut gear_part_ratios: Vec<u32> = vec![];
+
+    for symbol in &symbols {
+        let visited_numbers = symbol.visit(&mut numbers);
+        if visited_numbers.len() == 2 {
+            gear_part_ratios.push(
+                visited_numbers
+                    .first()
+                    .unwrap()
+                    .value
+                    .checked_mul(visited_numbers.last().unwrap().value)
+                    .ok_or(SchematicError::Overflow)?,
+            );
+        }
+    }
+
+    gear_part_ratios
+        .iter()
+        .try_fold(0u32, |total, ratio| total.checked_add(*ratio))
+        .ok_or(SchematicError::Overflow)
+}
+
+fn extract_numbers(data: &str, numbers_pattern: &Regex) -> Result<Vec<Number>, SchematicError> {
+    data.lines()
+        .enumerate()
+        .flat_map(|(row_index, line)| {
+            numbers_pattern
+                .captures_iter(line)
+                .map(|captures| transform_matching_numbers(row_index, &captures))
+                .collect::<Vec<Result<Number, SchematicError>>>()
+        })
+        .collect::<Result<Vec<Number>, SchematicError>>()
+}
+
+fn transform_matching_numbers(
+    row_index: usize,
+    captures: &Captures,
+) -> Result<Number, SchematicError> {
+    let number = captures.get(0).unwrap();
+    let (column_start_index, column_end_index) = (number.start(), number.end());
+    let value = number
+        .as_str()
+        .parse::<u32>()
+        .map_err(|_| SchematicError::NumberTooLarge {
+            line_number: row_index + 1,
+            column: column_start_index + 1,
+        })?;
+
+    Ok(Number {
+        row_index,
+        column_start_index,
+        column_end_index: column_end_index - 1, //the Regex crate has the end index as exclusive
+        value,
+        visited: false,
+    })
+}
+
+fn extract_symbols(data: &str, symbols_pattern: &Regex) -> Vec<Symbol> {
+    data.lines()
+        .enumerate()
+        .flat_map(|(row_index, line)| {
+            symbols_pattern
+                .captures_iter(line)
+                .map(|captures| transform_matching_symbols(row_index, &captures))
+                .collect::<Vec<Symbol>>()
+        })
+        .collect::<Vec<Symbol>>()
+}
+
+fn transform_matching_symbols(row_index: usize, captures: &Captures) -> Symbol {
+    let symbol = captures.get(0).unwrap();
+    let column_index = symbol.start();
+    Symbol {
+        row_index,
+        column_index,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_one_example_input() {
+        assert_eq!(
+            part_one(&FileLoader::load(YEAR, "03", TaskType::Example)),
+            Ok(4361)
+        );
+    }
+
+    #[test]
+    fn part_one_puzzle_input() {
+        assert_eq!(
+            part_one(&FileLoader::load(YEAR, "03", TaskType::Puzzle)),
+            Ok(556_057)
+        );
+    }
+
+    #[test]
+    fn part_two_example_input() {
+        assert_eq!(
+            part_two(&FileLoader::load(YEAR, "03", TaskType::Example)),
+            Ok(467_835)
+        );
+    }
+
+    #[test]
+    fn part_two_puzzle_input() {
+        assert_eq!(
+            part_two(&FileLoader::load(YEAR, "03", TaskType::Puzzle)),
+            Ok(82_824_352)
+        );
+    }
+
+    #[test]
+    fn symbols_on_the_edges() {
+        assert_eq!(part_one("*12\n3..\n..#"), Ok(12 + 3));
+        assert_eq!(part_two("*12\n3.."), Ok(36));
+    }
+
+    #[test]
+    fn oversized_numbers_are_errors() {
+        assert_eq!(
+            part_one("..\n.*99999999999"),
+            Err(SchematicError::NumberTooLarge {
+                line_number: 2,
+                column: 3
+            })
+        );
+        assert_eq!(part_two("4000000000*2"), Err(SchematicError::Overflow));
+    }
+}
@@ -0,0 +1,558 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Debug, Display, Formatter};
+
+use super::YEAR;
+use crate::{DayRunner, FileLoader, Part, TaskType};
+
+#[derive(Debug)]
+pub struct Day04;
+
+impl DayRunner for Day04 {
+    fn run(part: Part) {
+        let result = match part {
+            Part::PartOne => part_one(&FileLoader::load(YEAR, "04", TaskType::Puzzle)),
+            Part::PartTwo => part_two(
+                &FileLoader::load(YEAR, "04", TaskType::Puzzle),
+                &Overflow::Error,
+            ),
+        }
+        .unwrap_or_else(|error| panic!("{error}"));
+
+        Self::report_result(Self, part, result);
+    }
+}
+
+#[derive(Debug)]
+struct Card {
+    id: usize,
+    matches: usize,
+}
+
+impl Card {
+    fn new(line: &str, line_number: usize) -> Result<Self, CascadeError> {
+        fn number_line_as_vec(number_line: &str) -> Option<Vec<u32>> {
+            let numbers = number_line.split_whitespace().collect::<Vec<_>>();
+
+            numbers
+                .iter()
+                .map(|number| number.parse::<u32>().ok())
+                .collect()
+        }
+
+        let invalid = || CascadeError::InvalidCard(line_number);
+
+        let (card_id_and_numbers, drawn_numbers) =
+            line.trim().split_once('|').ok_or_else(invalid)?;
+        let drawn_numbers: HashSet<u32> = number_line_as_vec(drawn_numbers)
+            .ok_or_else(invalid)?
+            .into_iter()
+            .collect();
+
+        let (card_id, numbers) = card_id_and_numbers
+            .trim()
+            .split_once(':')
+            .ok_or_else(invalid)?;
+
+        let id = card_id.split_whitespace().last().ok_or_else(invalid)?;
+        let id = id.parse::<usize>().map_err(|_| invalid())?;
+
+        // The match count is all either part needs, so work it out once here rather than
+        // re-scanning the drawn numbers every time a card is scored.
+        let matches = number_line_as_vec(numbers)
+            .ok_or_else(invalid)?
+            .iter()
+            .filter(|number| drawn_numbers.contains(number))
+            .count();
+
+        Ok(Self { id, matches })
+    }
+
+    fn points(&self) -> Result<u32, CascadeError> {
+        match self.matches {
+            0 => Ok(0),
+            count => u32::try_from(count - 1)
+                .ok()
+                .and_then(|exponent| 2_u32.checked_pow(exponent))
+                .ok_or(CascadeError::Overflow),
+        }
+    }
+}
+
+fn cards(data: &str) -> Result<Vec<Card>, CascadeError> {
+    data.lines()
+        .enumerate()
+        .map(|(index, line)| Card::new(line, index + 1))
+        .collect()
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub(crate) fn parse(data: &str) -> Result<(), CascadeError> {
+    cards(data).map(drop)
+}
+
+pub(crate) fn part_one(data: &str) -> Result<u32, CascadeError> {
+    cards(data)?.iter().try_fold(0u32, |total, card| {
+        total
+            .checked_add(card.points()?)
+            .ok_or(CascadeError::Overflow)
+    })
+}
+
+/// Counts the scratchcards you end up with, cascading copies in card ID order.
+///
+/// # Errors
+///
+/// Returns an error for a line that is not a card, if a card ID is repeated, if a card wins
+/// copies past the last card and `overflow` is [`Overflow::Error`], or if there are more
+/// cards than fit in a u32.
+pub fn part_two(data: &str, overflow: &Overflow) -> Result<u32, CascadeError> {
+    let cards = ordered_cards(data)?;
+    let mut copies = vec![1u32; cards.len()];
+    let mut total_cards = 0u32;
+
+    for index in 0..cards.len() {
+        let instances = copies[index];
+        total_cards = total_cards
+            .checked_add(instances)
+            .ok_or(CascadeError::Overflow)?;
+        for copy in &mut copies[index + 1..=index + won(&cards, index, *overflow)?] {
+            *copy = copy.checked_add(instances).ok_or(CascadeError::Overflow)?;
+        }
+    }
+
+    Ok(total_cards)
+}
+
+/// What to do when a card wins copies of cards beyond the last one in the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Only copy the cards that exist, ignoring the rest of the winnings.
+    Clamp,
+    /// Treat the table as invalid.
+    Error,
+}
+
+/// Why a table of scratchcards could not be scored or played out.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CascadeError {
+    /// The line with this number is not `Card <id>: <numbers> | <numbers>`.
+    InvalidCard(usize),
+    DuplicateCardId(usize),
+    CopiesPastLastCard {
+        card_id: usize,
+        matches: usize,
+        cards_after: usize,
+    },
+    Overflow,
+}
+
+impl Display for CascadeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCard(line_number) => {
+                write!(f, "Line {line_number} is not `Card <id>: <numbers> | <numbers>`")
+            }
+            Self::DuplicateCardId(card_id) => write!(f, "Card {card_id} appears more than once"),
+            Self::CopiesPastLastCard {
+                card_id,
+                matches,
+                cards_after,
+            } => write!(
+                f,
+                "Card {card_id} wins copies of the next {matches} cards, but only {cards_after} follow it"
+            ),
+            Self::Overflow => write!(f, "The scratchcards overflow a u32"),
+        }
+    }
+}
+
+impl std::error::Error for CascadeError {}
+
+/// Parses the cards and puts them in ID order, which is the order copies cascade in.
+///
+/// IDs do not need to be contiguous or sorted in the input, but each must be unique.
+fn ordered_cards(data: &str) -> Result<Vec<Card>, CascadeError> {
+    let mut cards = cards(data)?;
+    cards.sort_by_key(|card| card.id);
+
+    if let Some(pair) = cards.windows(2).find(|pair| pair[0].id == pair[1].id) {
+        return Err(CascadeError::DuplicateCardId(pair[0].id));
+    }
+
+    Ok(cards)
+}
+
+/// How many of the following cards the card at `index` wins copies of.
+fn won(cards: &[Card], index: usize, overflow: Overflow) -> Result<usize, CascadeError> {
+    let card = &cards[index];
+    let cards_after = cards.len() - index - 1;
+    match (overflow, card.matches > cards_after) {
+        (_, false) | (Overflow::Clamp, true) => Ok(card.matches.min(cards_after)),
+        (Overflow::Error, true) => Err(CascadeError::CopiesPastLastCard {
+            card_id: card.id,
+            matches: card.matches,
+            cards_after,
+        }),
+    }
+}
+
+/// Plays out the scratchcards, recording which cards each card's copies came from.
+///
+/// # Errors
+///
+/// See [`part_two`].
+pub fn cascade(data: &str, overflow: &Overflow) -> Result<Cascade, CascadeError> {
+    let cards = ordered_cards(data)?;
+    let mut traces = cards
+        .iter()
+        .map(|card| CardTrace {
+            id: card.id,
+            matches: card.matches,
+            points: card.points().ok(),
+            copies: 0,
+            contributors: vec![],
+        })
+        .collect::<Vec<CardTrace>>();
+    // Checked as the copies cascade, so that the totals reported afterwards cannot overflow.
+    let mut total_cards = u32::try_from(cards.len()).map_err(|_| CascadeError::Overflow)?;
+
+    for (index, card) in cards.iter().enumerate() {
+        let won = won(&cards, index, *overflow)?;
+
+        // Every instance of this card, original or copy, wins one copy of each following card.
+        let instances = traces[index].instances();
+        for trace in &mut traces[index + 1..=index + won] {
+            total_cards = total_cards
+                .checked_add(instances)
+                .ok_or(CascadeError::Overflow)?;
+            trace.copies += instances;
+            trace.contributors.push((card.id, instances));
+        }
+    }
+
+    Ok(Cascade { cards: traces })
+}
+
+/// The outcome of playing out a table of scratchcards, in card ID order.
+#[derive(Debug)]
+pub struct Cascade {
+    pub cards: Vec<CardTrace>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CardTrace {
+    pub id: usize,
+    pub matches: usize,
+    /// The card's points, or `None` if they do not fit in a u32.
+    pub points: Option<u32>,
+    /// Copies won from earlier cards, not counting the original.
+    pub copies: u32,
+    /// The ID of each card that won copies of this one, with how many it won.
+    pub contributors: Vec<(usize, u32)>,
+}
+
+impl CardTrace {
+    const fn instances(&self) -> u32 {
+        self.copies + 1
+    }
+}
+
+impl Cascade {
+    #[must_use]
+    pub fn total_cards(&self) -> u32 {
+        self.cards.iter().map(CardTrace::instances).sum()
+    }
+
+    /// The card that received the most copies, favouring the lowest ID on a tie.
+    #[must_use]
+    pub fn max_copies(&self) -> Option<&CardTrace> {
+        self.cards.iter().rev().max_by_key(|card| card.copies)
+    }
+
+    /// How many cards there are with each number of matches.
+    #[must_use]
+    pub fn match_distribution(&self) -> BTreeMap<usize, usize> {
+        self.cards
+            .iter()
+            .fold(BTreeMap::new(), |mut distribution, card| {
+                *distribution.entry(card.matches).or_insert(0) += 1;
+                distribution
+            })
+    }
+
+    /// The points scored if every copy is scored alongside its original, saturating at
+    /// `u64::MAX`, which is also where a card whose points do not fit in a u32 leaves it.
+    #[must_use]
+    pub fn total_points(&self) -> u64 {
+        self.cards
+            .iter()
+            .map(|card| {
+                card.points.map_or(u64::MAX, |points| {
+                    u64::from(points) * u64::from(card.instances())
+                })
+            })
+            .fold(0, u64::saturating_add)
+    }
+}
+
+impl Display for Cascade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>6} {:>8} {:>8} {:>10}  From",
+            "Card", "Matches", "Points", "Copies"
+        )?;
+        for card in &self.cards {
+            let from = match card.contributors.as_slice() {
+                [] => "-".to_string(),
+                contributors => contributors
+                    .iter()
+                    .map(|(id, copies)| format!("{id} (x{copies})"))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            };
+
+            let points = card
+                .points
+                .map_or_else(|| "-".to_string(), |points| points.to_string());
+            writeln!(
+                f,
+                "{:>6} {:>8} {points:>8} {:>10}  {from}",
+                card.id, card.matches, card.copies
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Total cards: {}", self.total_cards())?;
+        if let Some(card) = self.max_copies() {
+            writeln!(f, "Max copies: {} (card {})", card.copies, card.id)?;
+        }
+        writeln!(f, "Total points with copies: {}", self.total_points())?;
+        writeln!(f, "Match distribution:")?;
+        for (matches, cards) in self.match_distribution() {
+            writeln!(f, "{matches:>6} matches: {cards} card(s)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The original solver, which checks each drawn number with `Vec::contains` and cascades copies
+/// through a map keyed by card ID. It is kept as a baseline for differential testing and
+/// shrinking.
+pub(crate) mod reference {
+    use std::collections::HashMap;
+
+    struct Card {
+        id: usize,
+        numbers: Vec<u32>,
+        drawn_numbers: Vec<u32>,
+    }
+
+    impl Card {
+        fn new(line: &str) -> Self {
+            fn number_line_as_vec(number_line: &str) -> Vec<u32> {
+                number_line
+                    .split_whitespace()
+                    .map(|number| number.parse::<u32>().unwrap())
+                    .collect()
+            }
+
+            let (card_id_and_numbers, drawn_numbers) = line.trim().split_once('|').unwrap();
+            let (card_id, numbers) = card_id_and_numbers.trim().split_once(':').unwrap();
+
+            Self {
+                id: card_id.split_whitespace().last().unwrap().parse().unwrap(),
+                numbers: number_line_as_vec(numbers),
+                drawn_numbers: number_line_as_vec(drawn_numbers),
+            }
+        }
+
+        fn winning_numbers(&self) -> usize {
+            self.numbers
+                .iter()
+                .filter(|number| self.drawn_numbers.contains(number))
+                .count()
+        }
+    }
+
+    pub fn part_one(data: &str) -> u32 {
+        data.lines()
+            .map(Card::new)
+            .map(|card| match card.winning_numbers() {
+                0 => 0,
+                count => 2_u32.pow((count - 1).try_into().unwrap()),
+            })
+            .sum()
+    }
+
+    pub fn part_two(data: &str) -> u32 {
+        let cards = data.lines().map(Card::new).collect::<Vec<Card>>();
+        let mut card_id_to_count = cards
+            .iter()
+            .map(|card| (card.id, 1_u32))
+            .collect::<HashMap<usize, u32>>();
+
+        for card in &cards {
+            for card_id in card.id + 1..=card.id + card.winning_numbers() {
+                *card_id_to_count.get_mut(&card_id).unwrap() += card_id_to_count[&card.id];
+            }
+        }
+
+        card_id_to_count.values().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_one_example_input() {
+        assert_eq!(
+            part_one(&FileLoader::load(YEAR, "04", TaskType::Example)),
+            Ok(13)
+        );
+    }
+
+    #[test]
+    fn part_one_puzzle_input() {
+        assert_eq!(
+            part_one(&FileLoader::load(YEAR, "04", TaskType::Puzzle)),
+            Ok(20855)
+        );
+    }
+
+    #[test]
+    fn part_two_example_input() {
+        assert_eq!(
+            part_two(
+                &FileLoader::load(YEAR, "04", TaskType::Example),
+                &Overflow::Error
+            ),
+            Ok(30)
+        );
+    }
+
+    #[test]
+    fn part_two_puzzle_input() {
+        assert_eq!(
+            part_two(
+                &FileLoader::load(YEAR, "04", TaskType::Puzzle),
+                &Overflow::Error
+            ),
+            Ok(5_489_600)
+        );
+    }
+
+    #[test]
+    fn cascade_example_input() {
+        let cascade = cascade(
+            &FileLoader::load(YEAR, "04", TaskType::Example),
+            &Overflow::Error,
+        )
+        .unwrap();
+
+        assert_eq!(
+            cascade.cards[3],
+            CardTrace {
+                id: 4,
+                matches: 1,
+                points: Some(1),
+                copies: 7,
+                contributors: vec![(1, 1), (2, 2), (3, 4)],
+            }
+        );
+        assert_eq!(
+            cascade.max_copies().map(|card| (card.id, card.copies)),
+            Some((5, 13))
+        );
+        assert_eq!(
+            cascade.match_distribution(),
+            BTreeMap::from([(0, 2), (1, 1), (2, 2), (4, 1)])
+        );
+        assert_eq!(cascade.total_points(), 28);
+    }
+
+    #[test]
+    fn part_two_unordered_non_contiguous_ids() {
+        let data = "Card 30: 1 2 | 8 9\nCard 10: 1 2 | 1 2\nCard 20: 5 | 6";
+
+        // Card 10 wins a copy each of cards 20 and 30, and card 20 wins nothing.
+        assert_eq!(part_two(data, &Overflow::Error), Ok(5));
+    }
+
+    #[test]
+    fn part_two_overflow_clamps_at_last_card() {
+        let data = "Card 1: 1 2 3 | 1 2 3\nCard 2: 4 | 4";
+
+        // Card 1 wins three copies but only card 2 exists; card 2 then overflows by one.
+        assert_eq!(part_two(data, &Overflow::Clamp), Ok(3));
+    }
+
+    #[test]
+    fn part_two_overflow_errors() {
+        let data = "Card 1: 1 2 3 | 1 2 3\nCard 2: 4 | 5";
+
+        assert_eq!(
+            part_two(data, &Overflow::Error),
+            Err(CascadeError::CopiesPastLastCard {
+                card_id: 1,
+                matches: 3,
+                cards_after: 1
+            })
+        );
+    }
+
+    #[test]
+    fn part_two_ignores_points_that_overflow() {
+        let numbers = (1..=40)
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        let data = std::iter::once(format!("Card 1: {numbers} | {numbers}"))
+            .chain((2..=41).map(|id| format!("Card {id}: 1 | 2")))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        // Card 1 scores 2^39 points, which only part one has to add up.
+        assert_eq!(part_one(&data), Err(CascadeError::Overflow));
+        assert_eq!(part_two(&data, &Overflow::Error), Ok(81));
+
+        let cascade = cascade(&data, &Overflow::Error).unwrap();
+        assert_eq!(cascade.cards[0].points, None);
+        assert_eq!(cascade.total_cards(), 81);
+        assert_eq!(cascade.total_points(), u64::MAX);
+    }
+
+    #[test]
+    fn part_two_rejects_duplicate_ids() {
+        let data = "Card 1: 1 | 1\nCard 2: 4 | 5\nCard 1: 7 | 8";
+
+        assert_eq!(
+            part_two(data, &Overflow::Clamp),
+            Err(CascadeError::DuplicateCardId(1))
+        );
+    }
+
+    #[test]
+    fn malformed_cards_are_errors() {
+        assert_eq!(
+            part_one("Card 1: 1 | 1\nCard 2: 1 2"),
+            Err(CascadeError::InvalidCard(2))
+        );
+        assert_eq!(
+            part_two("Card x: 1 | 1", &Overflow::Clamp),
+            Err(CascadeError::InvalidCard(1))
+        );
+
+        let many_matches = (1..=40)
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        assert_eq!(
+            part_one(&format!("Card 1: {many_matches} | {many_matches}")),
+            Err(CascadeError::Overflow)
+        );
+    }
+}
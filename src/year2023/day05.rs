@@ -0,0 +1,294 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use super::YEAR;
+use crate::{DayRunner, FileLoader, Part, TaskType};
+
+#[derive(Debug)]
+pub struct Day05;
+
+impl DayRunner for Day05 {
+    fn run(part: Part) {
+        let result = match part {
+            Part::PartOne => part_one(&FileLoader::load(YEAR, "05", TaskType::Puzzle)),
+            Part::PartTwo => part_two(&FileLoader::load(YEAR, "05", TaskType::Puzzle)),
+        }
+        .unwrap_or_else(|error| panic!("{error}"));
+
+        Self::report_result(Self, part, result);
+    }
+}
+
+pub(crate) fn part_one(data: &str) -> Result<u64, AlmanacError> {
+    let garden = Garden::new(data)?;
+    garden
+        .seed_numbers
+        .iter()
+        .map(|seed_number| garden.find_seed_location(*seed_number))
+        .min()
+        .ok_or(AlmanacError::NoSeeds)
+}
+
+pub(crate) fn part_two(data: &str) -> Result<u64, AlmanacError> {
+    let garden = Garden::new(data)?;
+    if garden.seed_numbers.len() % 2 == 1 {
+        return Err(AlmanacError::UnpairedSeed);
+    }
+
+    let mut locations = vec![];
+    for pair in garden.seed_numbers.chunks(2).filter(|pair| pair[1] > 0) {
+        let end = pair[0]
+            .checked_add(pair[1])
+            .ok_or(AlmanacError::SeedOverflow)?;
+        locations.extend(garden.find_range_locations(pair[0], end));
+    }
+
+    locations
+        .iter()
+        .map(|(start, _)| *start)
+        .min()
+        .ok_or(AlmanacError::NoSeeds)
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub(crate) fn parse(data: &str) -> Result<(), AlmanacError> {
+    Garden::new(data).map(drop)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AlmanacError {
+    MissingSeeds,
+    NoSeeds,
+    UnpairedSeed,
+    SeedOverflow,
+    InvalidLine(usize),
+    RangeOverflow(usize),
+}
+
+impl Display for AlmanacError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingSeeds => write!(f, "The almanac should start with `seeds:`"),
+            Self::NoSeeds => write!(f, "There are no seeds to plant"),
+            Self::UnpairedSeed => write!(f, "The seed ranges should come in pairs"),
+            Self::SeedOverflow => write!(f, "A seed range runs past u64::MAX"),
+            Self::InvalidLine(line_number) => write!(
+                f,
+                "Line {line_number} is neither a map header nor three numbers"
+            ),
+            Self::RangeOverflow(line_number) => {
+                write!(f, "The range on line {line_number} runs past u64::MAX")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AlmanacError {}
+
+struct Garden {
+    seed_numbers: Vec<u64>,
+    mappings: Vec<Vec<GardenRange>>,
+}
+
+#[derive(Debug)]
+struct GardenRange {
+    destination_start: u64,
+    source_start: u64,
+    amount: u64,
+}
+
+impl Garden {
+    fn new(data: &str) -> Result<Self, AlmanacError> {
+        fn parse_numbers(values: &str) -> Option<Vec<u64>> {
+            values
+                .split_whitespace()
+                .map(|value| value.parse::<u64>().ok())
+                .collect()
+        }
+
+        let mut lines = data.lines().map(str::trim).enumerate();
+        let seed_numbers = lines
+            .next()
+            .and_then(|(_, line)| line.strip_prefix("seeds:"))
+            .ok_or(AlmanacError::MissingSeeds)?;
+        let seed_numbers = parse_numbers(seed_numbers).ok_or(AlmanacError::InvalidLine(1))?;
+
+        // Each map starts with a `<source>-to-<destination> map:` header, followed by its ranges.
+        let mut mappings: Vec<Vec<GardenRange>> = vec![];
+        for (index, line) in lines.filter(|(_, line)| !line.is_empty()) {
+            let line_number = index + 1;
+            if line.ends_with("map:") {
+                mappings.push(vec![]);
+                continue;
+            }
+
+            let (Some(mapping), Some([destination_start, source_start, amount])) = (
+                mappings.last_mut(),
+                parse_numbers(line).and_then(|values| <[u64; 3]>::try_from(values).ok()),
+            ) else {
+                return Err(AlmanacError::InvalidLine(line_number));
+            };
+
+            // Both ends are checked here, so mapping a number within the range cannot overflow.
+            if source_start.checked_add(amount).is_none()
+                || destination_start.checked_add(amount).is_none()
+            {
+                return Err(AlmanacError::RangeOverflow(line_number));
+            }
+
+            mapping.push(GardenRange {
+                destination_start,
+                source_start,
+                amount,
+            });
+        }
+
+        Ok(Self {
+            seed_numbers,
+            mappings,
+        })
+    }
+
+    fn find_seed_location(&self, initial_number: u64) -> u64 {
+        let mut number = initial_number;
+
+        for mapping in &self.mappings {
+            for range in mapping {
+                if range.source_start <= number && number < range.source_start + range.amount {
+                    number = (number - range.source_start) + range.destination_start;
+                    break;
+                }
+            }
+        }
+
+        number
+    }
+
+    /// Maps the seeds in `start..end` to the location ranges they end up in, splitting the range
+    /// wherever it straddles the edge of a mapping range rather than mapping seed by seed.
+    fn find_range_locations(&self, start: u64, end: u64) -> Vec<(u64, u64)> {
+        let mut intervals = vec![(start, end)];
+
+        for mapping in &self.mappings {
+            let mut mapped = vec![];
+
+            // As with single seeds, the first range to cover a number wins, so only the pieces
+            // left unmatched are offered to later ranges.
+            for range in mapping {
+                let source_end = range.source_start + range.amount;
+                let mut unmatched = vec![];
+
+                for (start, end) in intervals {
+                    let overlap_start = start.max(range.source_start);
+                    let overlap_end = end.min(source_end);
+
+                    if overlap_start >= overlap_end {
+                        unmatched.push((start, end));
+                        continue;
+                    }
+
+                    mapped.push((
+                        overlap_start - range.source_start + range.destination_start,
+                        overlap_end - range.source_start + range.destination_start,
+                    ));
+                    if start < overlap_start {
+                        unmatched.push((start, overlap_start));
+                    }
+                    if overlap_end < end {
+                        unmatched.push((overlap_end, end));
+                    }
+                }
+
+                intervals = unmatched;
+            }
+
+            intervals.extend(mapped);
+        }
+
+        intervals
+    }
+}
+
+/// The original part two, which maps every seed in every range one at a time. It is kept as a
+/// baseline for differential testing and shrinking.
+pub(crate) mod reference {
+    use std::cmp::min;
+
+    use super::Garden;
+
+    pub fn part_two(data: &str) -> u64 {
+        let garden = Garden::new(data).unwrap_or_else(|error| panic!("{error}"));
+        let mut iter = garden.seed_numbers.iter();
+
+        let mut min_location = u64::MAX;
+
+        while let Some(start_seed) = iter.next() {
+            let amount = *iter.next().unwrap();
+            let ending_seed = *start_seed + amount;
+
+            let mut current_seed = *start_seed;
+            while current_seed < ending_seed {
+                let location = garden.find_seed_location(current_seed);
+                min_location = min(min_location, location);
+                current_seed += 1;
+            }
+        }
+
+        min_location
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_one_example_input() {
+        assert_eq!(
+            part_one(&FileLoader::load(YEAR, "05", TaskType::Example)),
+            Ok(35)
+        );
+    }
+
+    #[test]
+    fn part_one_puzzle_input() {
+        assert_eq!(
+            part_one(&FileLoader::load(YEAR, "05", TaskType::Puzzle)),
+            Ok(340_994_526)
+        );
+    }
+
+    #[test]
+    fn part_two_example_input() {
+        assert_eq!(
+            part_two(&FileLoader::load(YEAR, "05", TaskType::Example)),
+            Ok(46)
+        );
+    }
+
+    #[test]
+    fn part_two_puzzle_input() {
+        assert_eq!(
+            part_two(&FileLoader::load(YEAR, "05", TaskType::Puzzle)),
+            Ok(52_210_644)
+        );
+    }
+
+    #[test]
+    fn malformed_almanacs_are_errors() {
+        assert_eq!(part_one("soil: 1 2"), Err(AlmanacError::MissingSeeds));
+        assert_eq!(part_one("seeds:"), Err(AlmanacError::NoSeeds));
+        assert_eq!(part_two("seeds: 1 2 3"), Err(AlmanacError::UnpairedSeed));
+        assert_eq!(
+            part_one("seeds: 1\n\n1 2 3"),
+            Err(AlmanacError::InvalidLine(3))
+        );
+        assert_eq!(
+            part_one("seeds: 1\na-to-b map:\n18446744073709551615 0 2"),
+            Err(AlmanacError::RangeOverflow(3))
+        );
+        assert_eq!(
+            part_two("seeds: 18446744073709551615 2"),
+            Err(AlmanacError::SeedOverflow)
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::zip;
+
+use super::YEAR;
+use crate::{DayRunner, FileLoader, Part, TaskType};
+
+#[derive(Debug)]
+pub struct Day06;
+
+impl DayRunner for Day06 {
+    fn run(part: Part) {
+        let result = match part {
+            Part::PartOne => part_one(&FileLoader::load(YEAR, "06", TaskType::Puzzle)),
+            Part::PartTwo => part_two(&FileLoader::load(YEAR, "06", TaskType::Puzzle)),
+        }
+        .unwrap_or_else(|error| panic!("{error}"));
+
+        Self::report_result(Self, part, result);
+    }
+}
+
+pub(crate) fn part_one(data: &str) -> Result<u64, RaceError> {
+    Race::create_individual_races(data)?
+        .iter()
+        .try_fold(1u64, |product, race| {
+            product.checked_mul(race.number_of_winning_strategies())
+        })
+        .ok_or(RaceError::Overflow)
+}
+
+pub(crate) fn part_two(data: &str) -> Result<u64, RaceError> {
+    Ok(Race::create_merged_race(data)?.number_of_winning_strategies())
+}
+
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub(crate) fn parse(data: &str) -> Result<(), RaceError> {
+    Race::values(data).map(drop)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RaceError {
+    MissingLine(&'static str),
+    InvalidNumber(usize),
+    MismatchedRaces { times: usize, distances: usize },
+    NoRaces,
+    Overflow,
+}
+
+impl Display for RaceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingLine(name) => write!(f, "The {name} line is missing"),
+            Self::InvalidNumber(line_number) => {
+                write!(
+                    f,
+                    "Line {line_number} should be a label followed by numbers"
+                )
+            }
+            Self::MismatchedRaces { times, distances } => {
+                write!(f, "There are {times} times but {distances} distances")
+            }
+            Self::NoRaces => write!(f, "There are no races"),
+            Self::Overflow => write!(f, "The answer overflows a u64"),
+        }
+    }
+}
+
+impl std::error::Error for RaceError {}
+
+#[derive(Debug)]
+struct Race {
+    time: u64,
+    distance: u64,
+}
+
+impl Race {
+    /// The times and distances, checking there is one of each for every race.
+    fn values(data: &str) -> Result<(Vec<u64>, Vec<u64>), RaceError> {
+        let mut iter = data.lines();
+        let times = Self::get_values(iter.next().ok_or(RaceError::MissingLine("time"))?, 1)?;
+        let distances =
+            Self::get_values(iter.next().ok_or(RaceError::MissingLine("distance"))?, 2)?;
+
+        match (times.len(), distances.len()) {
+            (0, _) => Err(RaceError::NoRaces),
+            (times, distances) if times != distances => {
+                Err(RaceError::MismatchedRaces { times, distances })
+            }
+            _ => Ok((times, distances)),
+        }
+    }
+
+    fn create_individual_races(data: &str) -> Result<Vec<Self>, RaceError> {
+        let (times, distances) = Self::values(data)?;
+
+        Ok(zip(times, distances)
+            .map(|(time, distance)| Self { time, distance })
+            .collect::<Vec<Self>>())
+    }
+
+    fn create_merged_race(data: &str) -> Result<Self, RaceError> {
+        fn merge_numbers(vector: &[u64]) -> Result<u64, RaceError> {
+            vector
+                .iter()
+                .fold(String::new(), |total, part| format!("{total}{part}"))
+                .parse::<u64>()
+                .map_err(|_| RaceError::Overflow)
+        }
+
+        let (times, distances) = Self::values(data)?;
+
+        Ok(Self {
+            time: merge_numbers(&times)?,
+            distance: merge_numbers(&distances)?,
+        })
+    }
+
+    fn get_values(line: &str, line_number: usize) -> Result<Vec<u64>, RaceError> {
+        let split = line.split_whitespace().collect::<Vec<_>>();
+        split
+            .get(1..)
+            .ok_or(RaceError::InvalidNumber(line_number))?
+            .iter()
+            .map(|value| value.parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>()
+            .map_err(|_| RaceError::InvalidNumber(line_number))
+    }
+
+    /// Holding the button for `hold` milliseconds travels `hold * (time - hold)`, so the winning
+    /// holds are the integers strictly between the roots of `hold² - time·hold + distance`.
+    fn number_of_winning_strategies(&self) -> u64 {
+        let (time, distance) = (u128::from(self.time), u128::from(self.distance));
+        let wins = |hold: u128| hold * (time - hold) > distance;
+
+        let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+            return 0;
+        };
+
+        // The square root is only approximate once rounded, so nudge the shortest hold onto
+        // the exact boundary.
+        let mut shortest = (time - discriminant.isqrt()) / 2;
+        while shortest <= time / 2 && !wins(shortest) {
+            shortest += 1;
+        }
+        while shortest > 0 && wins(shortest - 1) {
+            shortest -= 1;
+        }
+
+        if shortest > time / 2 {
+            return 0;
+        }
+
+        // The winning holds are symmetric about half the race time.
+        (time - 2 * shortest + 1).try_into().unwrap()
+    }
+}
+
+/// The original solver, which tries every hold time in turn. It is kept as a baseline for
+/// differential testing and shrinking.
+pub(crate) mod reference {
+    use super::Race;
+
+    fn number_of_winning_strategies(race: &Race) -> u64 {
+        (1..=race.time)
+            .filter(|time| {
+                let speed = *time;
+                let time_remaining = race.time - speed;
+                let distance_travelled = speed * time_remaining;
+
+                distance_travelled > race.distance
+            })
+            .count()
+            .try_into()
+            .unwrap()
+    }
+
+    pub fn part_one(data: &str) -> u64 {
+        Race::create_individual_races(data)
+            .unwrap_or_else(|error| panic!("{error}"))
+            .iter()
+            .map(number_of_winning_strategies)
+            .product::<u64>()
+    }
+
+    pub fn part_two(data: &str) -> u64 {
+        number_of_winning_strategies(
+            &Race::create_merged_race(data).unwrap_or_else(|error| panic!("{error}")),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_one_example_input() {
+        assert_eq!(
+            part_one(&FileLoader::load(YEAR, "06", TaskType::Example)),
+            Ok(288)
+        );
+    }
+
+    #[test]
+    fn part_one_puzzle_input() {
+        assert_eq!(
+            part_one(&FileLoader::load(YEAR, "06", TaskType::Puzzle)),
+            Ok(633_080)
+        );
+    }
+
+    #[test]
+    fn part_two_example_input() {
+        assert_eq!(
+            part_two(&FileLoader::load(YEAR, "06", TaskType::Example)),
+            Ok(71503)
+        );
+    }
+
+    #[test]
+    fn part_two_puzzle_input() {
+        assert_eq!(
+            part_two(&FileLoader::load(YEAR, "06", TaskType::Puzzle)),
+            Ok(20_048_741)
+        );
+    }
+
+    #[test]
+    fn malformed_races_are_errors() {
+        assert_eq!(part_one("Time: 7"), Err(RaceError::MissingLine("distance")));
+        assert_eq!(
+            part_one("Time: 7\nDistance: x"),
+            Err(RaceError::InvalidNumber(2))
+        );
+        assert_eq!(part_one("Time:\nDistance:"), Err(RaceError::NoRaces));
+        assert_eq!(
+            part_one("Time: 7 8\nDistance: 9"),
+            Err(RaceError::MismatchedRaces {
+                times: 2,
+                distances: 1
+            })
+        );
+        assert_eq!(
+            part_two("Time: 99999999999 99999999999\nDistance: 1 1"),
+            Err(RaceError::Overflow)
+        );
+    }
+
+    #[test]
+    fn closed_form_at_the_limits() {
+        let race = Race {
+            time: u64::MAX,
+            distance: u64::MAX,
+        };
+
+        // Holding for 1ms only travels u64::MAX - 1, but every hold from 2ms to u64::MAX - 2 wins.
+        assert_eq!(race.number_of_winning_strategies(), u64::MAX - 3);
+        assert_eq!(
+            Race {
+                time: 4,
+                distance: 4
+            }
+            .number_of_winning_strategies(),
+            0
+        );
+    }
+}
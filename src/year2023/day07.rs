@@ -1,8 +1,9 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display, Formatter};
 use std::iter::zip;
 
+use super::YEAR;
 use crate::{DayRunner, FileLoader, Part, TaskType};
 
 #[derive(Debug)]
@@ -11,34 +12,68 @@ pub struct Day07;
 impl DayRunner for Day07 {
     fn run(part: Part) {
         let result = match part {
-            Part::PartOne => part_one(&FileLoader::load("07", &TaskType::Puzzle)),
-            Part::PartTwo => part_two(&FileLoader::load("07", &TaskType::Puzzle)),
-        };
+            Part::PartOne => part_one(&FileLoader::load(YEAR, "07", TaskType::Puzzle)),
+            Part::PartTwo => part_two(&FileLoader::load(YEAR, "07", TaskType::Puzzle)),
+        }
+        .unwrap_or_else(|error| panic!("{error}"));
 
         Self::report_result(Self, part, result);
     }
 }
 
-fn part_one(data: &str) -> u32 {
-    run(PokerHand::create_hands(data, &HandType::NonJokered))
+pub(crate) fn part_one(data: &str) -> Result<u32, HandError> {
+    run(PokerHand::create_hands(data, &HandType::NonJokered)?)
+}
+
+pub(crate) fn part_two(data: &str) -> Result<u32, HandError> {
+    run(PokerHand::create_hands(data, &HandType::Jokered)?)
 }
 
-fn part_two(data: &str) -> u32 {
-    run(PokerHand::create_hands(data, &HandType::Jokered))
+/// Parses the input without solving anything, so that parsing can be timed on its own.
+pub(crate) fn parse(data: &str) -> Result<(), HandError> {
+    PokerHand::create_hands(data, &HandType::NonJokered).map(drop)
 }
 
-fn run(mut poker_hands: Vec<PokerHand>) -> u32 {
+fn run(mut poker_hands: Vec<PokerHand>) -> Result<u32, HandError> {
     poker_hands.sort();
 
-    let scored_poker_hands = ScoredPokerHand::create_hands(&poker_hands);
-    let winnings = scored_poker_hands
+    let scored_poker_hands = ScoredPokerHand::create_hands(&poker_hands)?;
+    scored_poker_hands
         .iter()
-        .map(|hand| hand.bid * hand.score)
-        .sum();
+        .try_fold(0u32, |winnings, hand| {
+            hand.bid
+                .checked_mul(hand.score)
+                .and_then(|winning| winnings.checked_add(winning))
+        })
+        .ok_or(HandError::Overflow)
+}
 
-    winnings
+#[derive(Debug, PartialEq, Eq)]
+pub enum HandError {
+    /// The line with this number is not `<hand> <bid>`.
+    InvalidLine(usize),
+    /// The hand on the line with this number is not five cards from `AKQJT98765432`.
+    InvalidHand(usize),
+    Overflow,
 }
 
+impl Display for HandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLine(line_number) => {
+                write!(f, "Line {line_number} should be a hand and a bid")
+            }
+            Self::InvalidHand(line_number) => write!(
+                f,
+                "The hand on line {line_number} should be five cards from AKQJT98765432"
+            ),
+            Self::Overflow => write!(f, "The total winnings overflow a u32"),
+        }
+    }
+}
+
+impl std::error::Error for HandError {}
+
 #[derive(Debug, PartialEq, Ord, PartialOrd, Eq, Clone)]
 enum HandType {
     Jokered,
@@ -65,12 +100,26 @@ struct PokerHand {
 }
 
 impl PokerHand {
-    fn create_hands(data: &str, hand_type: &HandType) -> Vec<Self> {
+    fn create_hands(data: &str, hand_type: &HandType) -> Result<Vec<Self>, HandError> {
         let hand_data: Vec<(String, u32)> = data
             .lines()
-            .map(|line| line.split_once(' ').unwrap())
-            .map(|(cards, bid)| (cards.to_string(), bid.parse::<u32>().unwrap()))
-            .collect();
+            .enumerate()
+            .map(|(index, line)| {
+                let (cards, bid) = line
+                    .split_once(' ')
+                    .ok_or(HandError::InvalidLine(index + 1))?;
+                let bid = bid
+                    .trim()
+                    .parse::<u32>()
+                    .map_err(|_| HandError::InvalidLine(index + 1))?;
+
+                if cards.len() != 5 || !cards.chars().all(|card| "AKQJT98765432".contains(card)) {
+                    return Err(HandError::InvalidHand(index + 1));
+                }
+
+                Ok((cards.to_string(), bid))
+            })
+            .collect::<Result<Vec<(String, u32)>, HandError>>()?;
 
         let mut poker_hands: Vec<Self> = vec![];
 
@@ -121,11 +170,11 @@ impl PokerHand {
             });
         }
 
-        poker_hands
+        Ok(poker_hands)
     }
 
     fn char_to_count(cards: &str, hand_type: &HandType) -> HashMap<char, u32> {
-        return match hand_type {
+        match hand_type {
             HandType::Jokered => {
                 let mut char_to_count: HashMap<char, u32> = HashMap::new();
                 let joker_count = cards.chars().filter(|card| *card == 'J').count();
@@ -165,7 +214,7 @@ impl PokerHand {
 
                 char_to_count
             }
-        };
+        }
     }
 }
 
@@ -233,17 +282,15 @@ fn compare(
     other: &PokerHand,
     card_strength_order: &HashMap<char, u32>,
 ) -> Ordering {
-    // Need to look at the cards one by one to determine ordering.
-    let self_cards = this.cards.chars().collect::<Vec<char>>();
+    // Need to look at the cards one by one to determine ordering. Identical hands are equal.
+    let Some((self_card, other_card)) = zip(this.cards.chars(), other.cards.chars())
+        .find(|(self_card, other_card)| self_card != other_card)
+    else {
+        return Ordering::Equal;
+    };
 
-    let other_cards = other.cards.chars().collect::<Vec<char>>();
-    let mut index = 0;
-    while self_cards[index] == other_cards[index] {
-        index += 1;
-    }
-
-    let self_card_strength = card_strength_order.get(&self_cards[index]).unwrap();
-    let other_card_strength = card_strength_order.get(&other_cards[index]).unwrap();
+    let self_card_strength = card_strength_order.get(&self_card).unwrap();
+    let other_card_strength = card_strength_order.get(&other_card).unwrap();
 
     other_card_strength.cmp(self_card_strength)
 }
@@ -258,15 +305,17 @@ struct ScoredPokerHand {
 }
 
 impl ScoredPokerHand {
-    fn create_hands(hands: &Vec<PokerHand>) -> Vec<Self> {
+    fn create_hands(hands: &Vec<PokerHand>) -> Result<Vec<Self>, HandError> {
         let maximum_score = hands.len();
 
         zip((1..=maximum_score).rev(), hands)
-            .map(|(score, poker_hand)| Self {
-                cards: poker_hand.cards.clone(),
-                bid: poker_hand.bid,
-                poker_rank: poker_hand.poker_rank.clone(),
-                score: u32::try_from(score).unwrap(),
+            .map(|(score, poker_hand)| {
+                Ok(Self {
+                    cards: poker_hand.cards.clone(),
+                    bid: poker_hand.bid,
+                    poker_rank: poker_hand.poker_rank.clone(),
+                    score: u32::try_from(score).map_err(|_| HandError::Overflow)?,
+                })
             })
             .collect()
     }
@@ -278,28 +327,50 @@ mod tests {
 
     #[test]
     fn part_one_example_input() {
-        assert_eq!(part_one(&FileLoader::load("07", &TaskType::Example)), 6440);
+        assert_eq!(
+            part_one(&FileLoader::load(YEAR, "07", TaskType::Example)),
+            Ok(6440)
+        );
     }
 
     #[test]
     fn part_one_puzzle_input() {
         assert_eq!(
-            part_one(&FileLoader::load("07", &TaskType::Puzzle)),
-            248_559_379
+            part_one(&FileLoader::load(YEAR, "07", TaskType::Puzzle)),
+            Ok(248_559_379)
         );
     }
 
     #[test]
     fn part_two_example_input() {
-        assert_eq!(part_two(&FileLoader::load("07", &TaskType::Example)), 5905);
+        assert_eq!(
+            part_two(&FileLoader::load(YEAR, "07", TaskType::Example)),
+            Ok(5905)
+        );
     }
 
-    #[ignore]
+    #[ignore = "the part two puzzle answer is not known yet"]
     #[test]
     fn part_two_puzzle_input() {
         assert_eq!(
-            part_two(&FileLoader::load("07", &TaskType::Puzzle)),
-            20_048_741
+            part_two(&FileLoader::load(YEAR, "07", TaskType::Puzzle)),
+            Ok(20_048_741)
+        );
+    }
+
+    #[test]
+    fn identical_hands_tie() {
+        assert_eq!(part_one("32T3K 1\n32T3K 2"), Ok(2 + 2));
+    }
+
+    #[test]
+    fn malformed_hands_are_errors() {
+        assert_eq!(part_one("32T3K"), Err(HandError::InvalidLine(1)));
+        assert_eq!(part_one("32T3K 1\n32T3 2"), Err(HandError::InvalidHand(2)));
+        assert_eq!(part_two("32T3X 1"), Err(HandError::InvalidHand(1)));
+        assert_eq!(
+            part_one("32T3K 4294967295\nKK677 2"),
+            Err(HandError::Overflow)
         );
     }
 }